@@ -0,0 +1,220 @@
+// Test Utility: Counting Global Allocator
+//
+// 以前我们通过比较 `as_ptr()` 来“推测”有没有发生拷贝：
+// 地址相同 => 没拷贝 (Move)，地址不同 => 深拷贝 (Clone)。
+// 但这只能说明“数据在不在原地”，说不清“到底分配了几次、分配了多少字节”。
+//
+// 这个 crate 提供一个包装了系统分配器的 `CountingAlloc`：
+// - 每次 alloc / realloc / dealloc 都记账。
+// - 计数是线程局部的，因为 cargo test 默认多线程并行执行测试，
+//   别的测试的分配不会混进来。
+//
+// 用法（在需要断言分配次数的 crate 里，只在测试时安装）：
+//
+// #[cfg(test)]
+// #[global_allocator]
+// static GLOBAL: alloc_counter::CountingAlloc = alloc_counter::CountingAlloc::system();
+//
+// 然后在测试里：
+//
+// let s = alloc_counter::assert_allocations!(1, { String::from("hello") });
+//
+// C++ Comparison:
+// 类似于重载全局 operator new / operator delete 来统计分配次数。
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::ops::Sub;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 一段代码执行期间（或线程启动以来）的分配统计。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// 申请新内存的次数：alloc + realloc（扩容/缩容也算一次新申请）
+    pub allocations: usize,
+    /// 其中由 realloc 产生的次数
+    pub reallocations: usize,
+    /// 释放内存的次数
+    pub deallocations: usize,
+    /// 累计申请的字节数（realloc 按新的大小计）
+    pub bytes_allocated: usize,
+}
+
+impl Sub for AllocStats {
+    type Output = AllocStats;
+
+    fn sub(self, rhs: AllocStats) -> AllocStats {
+        AllocStats {
+            allocations: self.allocations - rhs.allocations,
+            reallocations: self.reallocations - rhs.reallocations,
+            deallocations: self.deallocations - rhs.deallocations,
+            bytes_allocated: self.bytes_allocated - rhs.bytes_allocated,
+        }
+    }
+}
+
+thread_local! {
+    // const 初始化 + Cell<usize>：访问时不会再触发分配，避免在分配器里递归
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static REALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static DEALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static BYTES_ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+// 只要有一次分配经过了 CountingAlloc，就说明它确实被装成了 #[global_allocator]
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+fn bump(counter: &'static std::thread::LocalKey<Cell<usize>>, by: usize) {
+    // try_with：线程退出、TLS 已销毁时静默跳过
+    let _ = counter.try_with(|n| n.set(n.get() + by));
+}
+
+/// 计数分配器，包装另一个分配器（默认是 System）。
+pub struct CountingAlloc<A = System> {
+    inner: A,
+}
+
+impl CountingAlloc<System> {
+    /// 包装系统分配器，可以直接用在 `static` 里。
+    pub const fn system() -> Self {
+        CountingAlloc { inner: System }
+    }
+}
+
+impl<A> CountingAlloc<A> {
+    pub const fn new(inner: A) -> Self {
+        CountingAlloc { inner }
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAlloc<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        bump(&ALLOCATIONS, 1);
+        bump(&BYTES_ALLOCATED, layout.size());
+        self.inner.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        bump(&ALLOCATIONS, 1);
+        bump(&BYTES_ALLOCATED, layout.size());
+        self.inner.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        bump(&ALLOCATIONS, 1);
+        bump(&REALLOCATIONS, 1);
+        bump(&BYTES_ALLOCATED, new_size);
+        self.inner.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        bump(&DEALLOCATIONS, 1);
+        self.inner.dealloc(ptr, layout)
+    }
+}
+
+/// 当前线程启动以来的累计统计。
+///
+/// 如果 `CountingAlloc` 没有被装成 `#[global_allocator]`，所有计数都会是 0，
+/// 断言“0 次分配”就会误通过，所以这里直接 panic 提醒。
+pub fn stats() -> AllocStats {
+    assert!(
+        INSTALLED.load(Ordering::Relaxed),
+        "alloc_counter::CountingAlloc is not installed as #[global_allocator]"
+    );
+    AllocStats {
+        allocations: ALLOCATIONS.with(Cell::get),
+        reallocations: REALLOCATIONS.with(Cell::get),
+        deallocations: DEALLOCATIONS.with(Cell::get),
+        bytes_allocated: BYTES_ALLOCATED.with(Cell::get),
+    }
+}
+
+/// 执行闭包，返回 (结果, 期间当前线程的分配统计)。
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    let before = stats();
+    let ret = f();
+    let after = stats();
+    (ret, after - before)
+}
+
+/// 断言代码块恰好申请了 `n` 次内存（alloc + realloc），并返回代码块的值。
+///
+/// 代码块直接在当前作用域里执行（不是闭包），所以可以自由地 move 或借用外部变量。
+#[macro_export]
+macro_rules! assert_allocations {
+    ($n:expr, $body:block) => {{
+        let before = $crate::stats();
+        let ret = $body;
+        let diff = $crate::stats() - before;
+        assert_eq!(
+            diff.allocations, $n,
+            "expected {} allocation(s), got {:?}",
+            $n, diff
+        );
+        ret
+    }};
+}
+
+/// 断言代码块恰好申请了 `bytes` 字节（realloc 按新的大小计），并返回代码块的值。
+#[macro_export]
+macro_rules! assert_bytes_allocated {
+    ($bytes:expr, $body:block) => {{
+        let before = $crate::stats();
+        let ret = $body;
+        let diff = $crate::stats() - before;
+        assert_eq!(
+            diff.bytes_allocated, $bytes,
+            "expected {} byte(s) allocated, got {:?}",
+            $bytes, diff
+        );
+        ret
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static GLOBAL: CountingAlloc = CountingAlloc::system();
+
+    #[test]
+    fn test_counts_alloc_and_dealloc() {
+        let ((), s) = measure(|| {
+            let v: Vec<u8> = Vec::with_capacity(10);
+            drop(v);
+        });
+        assert_eq!(s.allocations, 1);
+        assert_eq!(s.deallocations, 1);
+        assert_eq!(s.bytes_allocated, 10);
+    }
+
+    #[test]
+    fn test_counts_realloc() {
+        let mut v: Vec<u8> = Vec::with_capacity(4);
+        let ((), s) = measure(|| v.extend_from_slice(&[0; 16]));
+        assert_eq!(s.allocations, 1);
+        assert_eq!(s.reallocations, 1);
+        assert_eq!(s.bytes_allocated, 16);
+    }
+
+    #[test]
+    fn test_macros() {
+        // 字面量不分配
+        let s: &str = assert_allocations!(0, { "hello" });
+        // String::from 分配一次，刚好 5 字节
+        let owned = assert_bytes_allocated!(5, { String::from(s) });
+        // 代码块里可以 move 外部变量
+        let moved = assert_allocations!(0, { owned });
+        assert_eq!(moved, "hello");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 0 allocation(s)")]
+    fn test_assert_allocations_fails() {
+        assert_allocations!(0, { Box::new(1) });
+    }
+}
@@ -0,0 +1,13 @@
+// source: topic04_lifetimes/src/main.rs (longest)
+// expect: E0106
+//
+// 两个引用参数、返回一个引用：编译器不知道返回值借用的是 x 还是 y，
+// 必须显式写出 <'a>。
+
+pub fn longest(x: &str, y: &str) -> &str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
@@ -0,0 +1,14 @@
+// source: topic02_slices/src/rust_str.rs (test_type_str, test_type_mut_str_binding)
+// expect: E0308, E0277
+//
+// 两行都原样照抄自 rust_str.rs 里注释掉的错误示范。
+// E0277: str 是 DST，大小在编译期未知，不能直接放在栈上。
+// E0308: "hello" 的类型是 &str，不是 str，所以同一行还会报类型不匹配。
+
+pub fn main() {
+    let s: str = "hello"; // Error: the size for values of type `str` cannot be known at compilation time
+}
+
+pub fn binding() {
+    let mut s: str = "hello"; 
+}
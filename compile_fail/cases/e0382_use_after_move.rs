@@ -0,0 +1,11 @@
+// source: topic03_ownership/src/main.rs (Move Semantics)
+// expect: E0382
+//
+// 赋值是 move：s1 的所有权转移给了 s2，之后 s1 不能再用。
+
+pub fn main() {
+    let s1 = String::from("hello");
+    let s2 = s1;
+    println!("{}", s1);
+    println!("{}", s2);
+}
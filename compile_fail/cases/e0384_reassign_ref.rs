@@ -0,0 +1,13 @@
+// source: topic02_slices/src/rust_str.rs (test_mut_ref_semantics)
+// expect: E0384
+//
+// r1 是可变引用，但绑定本身不可变：不能让它改指 s2。
+
+pub fn main() {
+    let mut s1 = String::from("s1");
+    let mut s2 = String::from("s2");
+    let r1: &mut String = &mut s1;
+    r1.push_str("_modified");
+    r1 = &mut s2;
+    r1.push_str("_again");
+}
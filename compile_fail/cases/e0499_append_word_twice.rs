@@ -0,0 +1,17 @@
+// source: topic03_ownership/src/arena.rs
+// expect: E0499
+//
+// 两次 append_word 的结果同时存活 = 同时存在两个 &mut s。
+// 和 e0502_append_word.rs 一样，编译的是真正的 topic03_ownership::append_word。
+
+#[path = "../../topic03_ownership/src/lib.rs"]
+mod topic03_ownership;
+
+use topic03_ownership::append_word;
+
+pub fn main() {
+    let mut s = String::from("a");
+    let r1 = append_word(&mut s, "b");
+    let r2 = append_word(&mut s, "c");
+    println!("{} {}", r1, r2);
+}
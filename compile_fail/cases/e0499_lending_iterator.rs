@@ -0,0 +1,35 @@
+// source: topic04_lifetimes/src/lending.rs (LendingIterator)
+// expect: E0499
+//
+// lending iterator 返回的 Item 借用了 &mut self：上一项还活着时不能再调用 next()。
+
+pub trait LendingIterator {
+    type Item<'a>
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+}
+
+pub struct Lines {
+    buf: String,
+}
+
+impl LendingIterator for Lines {
+    type Item<'a>
+        = &'a str
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<&str> {
+        self.buf.push('x');
+        Some(&self.buf)
+    }
+}
+
+pub fn main() {
+    let mut lines = Lines { buf: String::new() };
+    let first = lines.next();
+    let second = lines.next();
+    println!("{:?} {:?}", first, second);
+}
@@ -0,0 +1,20 @@
+// source: topic03_ownership/src/lib.rs (test_append_world)
+// expect: E0502
+//
+// append_word 返回的 &str 延续了 &mut s 的可变借用：
+// s3 还要用的时候，不能再读 s。
+//
+// 直接编译真正的 topic03_ownership/src/lib.rs，用的是它的 append_word 签名
+// (s: &'a mut String, s2: &'a str)，而不是这里重新写的一份。
+
+#[path = "../../topic03_ownership/src/lib.rs"]
+mod topic03_ownership;
+
+use topic03_ownership::append_word;
+
+pub fn main() {
+    let mut s = String::from("HELLO");
+    let s3 = append_word(&mut s, "zhr");
+    assert_eq!(s, "HELLOzhr");
+    assert_eq!(s3, "HELLOzhr");
+}
@@ -0,0 +1,13 @@
+// source: topic03_ownership/src/main.rs (Restriction example)
+// expect: E0502
+//
+// r1、r2 还要用，期间不能再创建可变借用。
+
+pub fn main() {
+    let mut s4 = String::from("Rust");
+    let r1 = &s4;
+    let r2 = &s4;
+    let r3 = &mut s4;
+    r3.push('!');
+    println!("r1: {}, r2: {}", r1, r2);
+}
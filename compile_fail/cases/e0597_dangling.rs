@@ -0,0 +1,20 @@
+// source: topic04_lifetimes/examples/dangling.rs
+// expect: E0597
+//
+// 返回值的生命周期不能比入参长：r 借用了 s，s 先死了。
+
+fn dangerous_ref<'a, 'b>(s: &'a str) -> &'b str
+where
+    'a: 'b,
+{
+    s
+}
+
+pub fn main() {
+    let r;
+    {
+        let s = String::from("hello");
+        r = dangerous_ref(&s);
+    } // s 在这里被销毁
+    println!("{}", r);
+}
@@ -0,0 +1,10 @@
+// source: topic02_slices/src/rust_str.rs (test_type_mut_ref_str)
+// expect: E0599
+//
+// &mut str 只能原地修改，没有 push：长度变了就需要重新分配，只有 String 能做。
+
+pub fn main() {
+    let mut s = String::from("hello");
+    let slice: &mut str = &mut s[..];
+    slice.push('!');
+}
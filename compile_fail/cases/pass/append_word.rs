@@ -0,0 +1,13 @@
+// 对照：e0502_append_word.rs 调换顺序，s3 用完之后再读 s
+
+#[path = "../../../topic03_ownership/src/lib.rs"]
+mod topic03_ownership;
+
+use topic03_ownership::append_word;
+
+pub fn main() {
+    let mut s = String::from("HELLO");
+    let s3 = append_word(&mut s, "zhr");
+    assert_eq!(s3, "HELLOzhr");
+    assert_eq!(s, "HELLOzhr");
+}
@@ -0,0 +1,9 @@
+// 对照：e0106_missing_lifetime.rs 加上生命周期标注后可以编译
+
+pub fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
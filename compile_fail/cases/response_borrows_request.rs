@@ -0,0 +1,17 @@
+// source: topic04_lifetimes/src/pipeline.rs (Echo)
+// expect: E0621
+//
+// Processor 的返回值只能借用 &'p self / Context，请求的生命周期是匿名的，
+// 直接返回 req.body 会被拒绝：编译器要求 req 的借用也必须是 'p。
+
+pub struct Request<'c> {
+    pub body: &'c str,
+}
+
+pub struct Echo;
+
+impl Echo {
+    pub fn process<'p>(&'p self, req: &Request<'_>) -> &'p str {
+        req.body
+    }
+}
@@ -0,0 +1,301 @@
+// Compile-Fail Harness: 检查“❌ 无法编译”的代码确实无法编译
+//
+// 教学代码里有很多注释掉的错误示范：
+//
+// // println!("{}", s1); // Error: value borrowed here after move
+//
+// 注释不会被编译器检查。如果哪天规则变了（比如 NLL 让某段代码变得合法了），
+// 或者注释里写错了错误码，没有人会发现。
+//
+// 这个 crate 把每个错误示范放进 cases/ 下的一个独立文件，文件头声明期望的错误码：
+//
+// // source: topic03_ownership/src/main.rs (Move Semantics)
+// // expect: E0382
+//
+// 然后直接调用 rustc 编译它（不依赖 trybuild 等第三方库，离线可用），断言：
+// 1. 编译失败；
+// 2. 实际出现的错误码集合和 expect 声明的完全一致。
+//
+// 错误示范用到库里的函数时，用例不重新抄一份，而是用 #[path] 直接编译真正的源码：
+//
+// #[path = "../../topic03_ownership/src/lib.rs"]
+// mod topic03_ownership;
+//
+// 这样库的签名一改，用例检查的也跟着变（被引入的文件不能在非测试代码里用 crate:: 路径）。
+//
+// cases/pass/ 下放的是对应的正确写法，必须能编译通过，用来证明 harness 本身没坏
+// （比如 rustc 找不到时，所有 fail case 都会“失败”，但 pass case 会暴露问题）。
+//
+// 运行：cargo test -p compile_fail，或 cargo run -p compile_fail 查看报告。
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 一个测试用例：一个独立的 .rs 文件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    pub path: PathBuf,
+    /// 来自哪个文件的错误示范（文件头 `// source:`）
+    pub source: Option<String>,
+    /// 期望的错误码（文件头 `// expect:`），为空表示必须编译通过
+    pub expected: BTreeSet<String>,
+}
+
+impl Case {
+    /// 读取文件头里的 `// source:` 和 `// expect:` 注释
+    pub fn load(path: &Path) -> io::Result<Case> {
+        let text = fs::read_to_string(path)?;
+        let mut case = Case {
+            path: path.to_path_buf(),
+            source: None,
+            expected: BTreeSet::new(),
+        };
+        for line in text.lines().take_while(|l| l.starts_with("//")) {
+            let line = line.trim_start_matches('/').trim();
+            if let Some(source) = line.strip_prefix("source:") {
+                case.source = Some(source.trim().to_string());
+            } else if let Some(codes) = line.strip_prefix("expect:") {
+                case.expected
+                    .extend(codes.split([',', ' ']).filter(|c| !c.is_empty()).map(String::from));
+            }
+        }
+        Ok(case)
+    }
+
+    pub fn name(&self) -> String {
+        self.path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    pub fn should_fail(&self) -> bool {
+        !self.expected.is_empty()
+    }
+}
+
+/// 读取目录下所有的 .rs 用例（不递归），按文件名排序
+pub fn load_cases(dir: &Path) -> io::Result<Vec<Case>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    paths.retain(|p| p.extension().is_some_and(|ext| ext == "rs"));
+    paths.sort();
+    paths.iter().map(|p| Case::load(p)).collect()
+}
+
+/// rustc 报出的一条错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// 编译一个文件（只做类型检查和借用检查，不生成代码），返回所有错误。
+/// 编译通过时返回空 Vec。
+pub fn compile(path: &Path) -> io::Result<Vec<Diagnostic>> {
+    let out_dir = TempDir::new()?;
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let output = Command::new(rustc)
+        .args(["--edition", "2021", "--crate-type", "lib", "--emit=metadata"])
+        .args(["--error-format=short", "--cap-lints", "allow"])
+        .arg("--crate-name")
+        .arg("compile_fail_case")
+        .arg("--out-dir")
+        .arg(&out_dir.0)
+        .arg(path)
+        .output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let diagnostics: Vec<Diagnostic> = stderr.lines().filter_map(parse_error_line).collect();
+    if !output.status.success() && diagnostics.is_empty() {
+        // 失败了却没有任何可识别的错误：多半是 rustc 本身出了问题
+        return Err(io::Error::other(format!("rustc failed: {}", stderr.trim())));
+    }
+    Ok(diagnostics)
+}
+
+// rustc 的输出目录。每次编译一个新目录（测试会并行调用 compile），用完即删。
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> io::Result<TempDir> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let n = NEXT.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("compile_fail-{}-{}", std::process::id(), n));
+        fs::create_dir_all(&path)?;
+        Ok(TempDir(path))
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+// short 格式的一行：`file.rs:9:20: error[E0382]: borrow of moved value: ...`
+// 不带错误码的：`file.rs:9:20: error: lifetime may not live long enough`
+// "error: aborting due to ..." 这样的汇总行没有位置信息，不算。
+fn parse_error_line(line: &str) -> Option<Diagnostic> {
+    let at = line.find(": error")?;
+    let rest = &line[at + ": error".len()..];
+    let (code, message) = match rest.strip_prefix('[') {
+        Some(tail) => {
+            let (code, message) = tail.split_once("]: ")?;
+            (Some(code.to_string()), message)
+        }
+        None => (None, rest.strip_prefix(": ")?),
+    };
+    Some(Diagnostic {
+        code,
+        message: message.to_string(),
+    })
+}
+
+/// 用例没有按预期失败（或通过）的原因
+#[derive(Debug)]
+pub enum Failure {
+    /// 期望编译失败，结果通过了
+    Compiled,
+    /// 期望编译通过，结果失败了
+    Rejected(Vec<Diagnostic>),
+    /// 失败了，但错误码和声明的不一致
+    Mismatch {
+        expected: BTreeSet<String>,
+        actual: Vec<Diagnostic>,
+    },
+    Io(io::Error),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let messages = |diags: &[Diagnostic]| {
+            diags
+                .iter()
+                .map(|d| format!("{}: {}", d.code.as_deref().unwrap_or("error"), d.message))
+                .collect::<Vec<_>>()
+                .join("; ")
+        };
+        match self {
+            Failure::Compiled => write!(f, "expected a compile error, but it compiled"),
+            Failure::Rejected(actual) => write!(f, "expected to compile, got [{}]", messages(actual)),
+            Failure::Mismatch { expected, actual } => {
+                let expected: Vec<&str> = expected.iter().map(String::as_str).collect();
+                write!(f, "expected [{}], got [{}]", expected.join(", "), messages(actual))
+            }
+            Failure::Io(e) => write!(f, "io error: {}", e),
+        }
+    }
+}
+
+/// 编译一个用例并和声明的期望比较
+pub fn check(case: &Case) -> Result<(), Failure> {
+    let actual = compile(&case.path).map_err(Failure::Io)?;
+    match (case.should_fail(), actual.is_empty()) {
+        (true, true) => Err(Failure::Compiled),
+        (false, false) => Err(Failure::Rejected(actual)),
+        (false, true) => Ok(()),
+        (true, false) => {
+            let codes: BTreeSet<String> = actual.iter().filter_map(|d| d.code.clone()).collect();
+            if codes == case.expected {
+                Ok(())
+            } else {
+                Err(Failure::Mismatch {
+                    expected: case.expected.clone(),
+                    actual,
+                })
+            }
+        }
+    }
+}
+
+/// cases/ 目录
+pub fn cases_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("cases")
+}
+
+/// 检查 cases/ 和 cases/pass/ 下的全部用例，返回每个用例的结果
+pub fn run_all() -> io::Result<Vec<(Case, Result<(), Failure>)>> {
+    let mut cases = load_cases(&cases_dir())?;
+    cases.extend(load_cases(&cases_dir().join("pass"))?);
+    Ok(cases
+        .into_iter()
+        .map(|case| {
+            let result = check(&case);
+            (case, result)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_line() {
+        assert_eq!(
+            parse_error_line("a.rs:9:20: error[E0382]: borrow of moved value: `s1`"),
+            Some(Diagnostic {
+                code: Some(String::from("E0382")),
+                message: String::from("borrow of moved value: `s1`"),
+            })
+        );
+        assert_eq!(
+            parse_error_line("a.rs:3:5: error: lifetime may not live long enough"),
+            Some(Diagnostic {
+                code: None,
+                message: String::from("lifetime may not live long enough"),
+            })
+        );
+        assert_eq!(parse_error_line("error: aborting due to 1 previous error"), None);
+        assert_eq!(parse_error_line("a.rs:1:1: warning: unused variable"), None);
+    }
+
+    #[test]
+    fn test_case_header() {
+        let case = Case::load(&cases_dir().join("e0382_use_after_move.rs")).unwrap();
+        assert_eq!(case.name(), "e0382_use_after_move");
+        assert_eq!(case.source.as_deref(), Some("topic03_ownership/src/main.rs (Move Semantics)"));
+        assert_eq!(case.expected, BTreeSet::from([String::from("E0382")]));
+
+        let pass = Case::load(&cases_dir().join("pass").join("longest.rs")).unwrap();
+        assert!(!pass.should_fail());
+    }
+
+    #[test]
+    fn test_all_cases() {
+        let results = run_all().unwrap();
+        let fail_cases = results.iter().filter(|(c, _)| c.should_fail()).count();
+        assert!(fail_cases >= 5, "expected compile-fail cases, found {}", fail_cases);
+
+        let failures: Vec<String> = results
+            .iter()
+            .filter_map(|(case, result)| {
+                result
+                    .as_ref()
+                    .err()
+                    .map(|e| format!("{}: {}", case.name(), e))
+            })
+            .collect();
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+
+    #[test]
+    fn test_expected_codes_covered() {
+        let cases = load_cases(&cases_dir()).unwrap();
+        let codes: BTreeSet<&str> = cases
+            .iter()
+            .flat_map(|c| c.expected.iter().map(String::as_str))
+            .collect();
+        for code in ["E0106", "E0499", "E0502", "E0382", "E0597"] {
+            assert!(codes.contains(code), "no case for {}", code);
+        }
+    }
+}
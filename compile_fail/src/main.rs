@@ -0,0 +1,36 @@
+// 逐个编译 cases/ 下的错误示范，打印每个用例的结果
+
+fn main() {
+    let results = match compile_fail::run_all() {
+        Ok(results) => results,
+        Err(e) => {
+            eprintln!("failed to run cases: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let mut failed = 0;
+    for (case, result) in &results {
+        let expected: Vec<&str> = case.expected.iter().map(String::as_str).collect();
+        let expected = if expected.is_empty() {
+            String::from("compiles")
+        } else {
+            expected.join(", ")
+        };
+        match result {
+            Ok(()) => println!("✅ {:<28} {}", case.name(), expected),
+            Err(e) => {
+                failed += 1;
+                println!("❌ {:<28} {}", case.name(), e);
+            }
+        }
+        if let Some(source) = &case.source {
+            println!("   from {}", source);
+        }
+    }
+
+    println!("\n{} cases, {} failed", results.len(), failed);
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
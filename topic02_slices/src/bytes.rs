@@ -0,0 +1,415 @@
+// Shared Byte Buffers: 便宜的 clone 和切片
+//
+// rust_str 的测试里对比了两种极端：
+// - Move：只拷贝栈上的 (ptr, len, cap)，但原变量失效。
+// - Clone：原变量有效，但堆上的数据整个深拷贝一份。
+//
+// 如果数据创建之后就不再修改，我们其实可以两全其美：
+// 堆数据放进引用计数的 Arc 里，clone 只是计数 +1；
+// 切片只是记录一个 [start, end) 区间，也共享同一块存储。
+//
+// 内存布局：
+// Bytes A [arc][start=0][end=11] ──┐
+// Bytes B [arc][start=6][end=11] ──┼──> Arc { count: 2, data: [h, e, l, l, o, _, w, o, r, l, d] }
+//
+// - BytesMut：可变的构建器（内部就是 Vec<u8>），写完后 freeze() 变成 Bytes。
+// - StrView：包装 Bytes，保证内容是合法 UTF-8，可以当 &str 用。
+//
+// C++ Comparison:
+// Bytes 类似 std::shared_ptr<const std::vector<uint8_t>> + 偏移/长度，
+// StrView 类似一个自己持有共享所有权的 std::string_view。
+
+use std::fmt;
+use std::ops::{Bound, Deref, DerefMut, RangeBounds};
+use std::str::Utf8Error;
+use std::sync::Arc;
+
+// 把任意 RangeBounds 转换成 [start, end)，越界时 panic（和切片语义一致）
+fn resolve_range(range: impl RangeBounds<usize>, len: usize) -> (usize, usize) {
+    let start = match range.start_bound() {
+        Bound::Included(&n) => n,
+        Bound::Excluded(&n) => n + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&n) => n + 1,
+        Bound::Excluded(&n) => n,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "range start {} > end {}", start, end);
+    assert!(end <= len, "range end {} out of bounds (len {})", end, len);
+    (start, end)
+}
+
+/// 不可变、引用计数的字节缓冲区。clone 和 slice 都是 O(1)，共享同一块存储。
+#[derive(Clone)]
+pub struct Bytes {
+    // Arc<Vec<u8>> 而不是 Arc<[u8]>：从 Vec 转换时不需要再拷贝一次数据
+    data: Arc<Vec<u8>>,
+    start: usize,
+    end: usize,
+}
+
+impl Bytes {
+    pub fn new() -> Bytes {
+        Bytes::from(Vec::new())
+    }
+
+    /// 拷贝一份切片的数据（唯一一次拷贝）
+    pub fn copy_from_slice(data: &[u8]) -> Bytes {
+        Bytes::from(data.to_vec())
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data[self.start..self.end]
+    }
+
+    /// O(1) 子切片：只调整区间并把引用计数 +1，不拷贝数据
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> Bytes {
+        let (start, end) = resolve_range(range, self.len());
+        Bytes {
+            data: Arc::clone(&self.data),
+            start: self.start + start,
+            end: self.start + end,
+        }
+    }
+
+    /// 在 `at` 处一分为二：self 保留 [at, len)，返回 [0, at)。两者共享存储。
+    pub fn split_to(&mut self, at: usize) -> Bytes {
+        let head = self.slice(..at);
+        self.start += at;
+        head
+    }
+
+    /// 两个 Bytes 是否共享同一块底层存储
+    pub fn shares_storage(&self, other: &Bytes) -> bool {
+        Arc::ptr_eq(&self.data, &other.data)
+    }
+
+    /// 共享这块存储的 Bytes 个数
+    pub fn ref_count(&self) -> usize {
+        Arc::strong_count(&self.data)
+    }
+}
+
+impl Default for Bytes {
+    fn default() -> Bytes {
+        Bytes::new()
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    // 接管 Vec 的堆内存，不拷贝
+    fn from(v: Vec<u8>) -> Bytes {
+        let end = v.len();
+        Bytes {
+            data: Arc::new(v),
+            start: 0,
+            end,
+        }
+    }
+}
+
+impl From<String> for Bytes {
+    fn from(s: String) -> Bytes {
+        Bytes::from(s.into_bytes())
+    }
+}
+
+impl From<&str> for Bytes {
+    fn from(s: &str) -> Bytes {
+        Bytes::copy_from_slice(s.as_bytes())
+    }
+}
+
+impl Deref for Bytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for Bytes {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl PartialEq for Bytes {
+    fn eq(&self, other: &Bytes) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for Bytes {}
+
+impl PartialEq<[u8]> for Bytes {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl PartialEq<&[u8]> for Bytes {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.as_slice() == *other
+    }
+}
+
+impl fmt::Debug for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "b\"{}\"", self.as_slice().escape_ascii())
+    }
+}
+
+/// 可变的字节构建器。写完之后 freeze() 成为不可变、可共享的 Bytes。
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct BytesMut {
+    buf: Vec<u8>,
+}
+
+impl BytesMut {
+    pub fn new() -> BytesMut {
+        BytesMut::default()
+    }
+
+    pub fn with_capacity(capacity: usize) -> BytesMut {
+        BytesMut {
+            buf: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn extend_from_slice(&mut self, data: &[u8]) {
+        self.buf.extend_from_slice(data);
+    }
+
+    pub fn push(&mut self, byte: u8) {
+        self.buf.push(byte);
+    }
+
+    pub fn push_str(&mut self, s: &str) {
+        self.buf.extend_from_slice(s.as_bytes());
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// 冻结：把 Vec 交给 Arc，之后只能读不能写。O(1)，不拷贝数据。
+    pub fn freeze(self) -> Bytes {
+        Bytes::from(self.buf)
+    }
+}
+
+impl Deref for BytesMut {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf
+    }
+}
+
+impl DerefMut for BytesMut {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buf
+    }
+}
+
+impl fmt::Debug for BytesMut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "b\"{}\"", self.buf.escape_ascii())
+    }
+}
+
+/// 保证是合法 UTF-8 的 Bytes：可以像 &str 一样使用，clone 和切片同样是 O(1)。
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct StrView {
+    // 不变量：bytes 的内容永远是合法 UTF-8
+    bytes: Bytes,
+}
+
+impl StrView {
+    /// 校验一次 UTF-8，之后的 as_str() 都不需要再校验
+    pub fn from_utf8(bytes: Bytes) -> Result<StrView, Utf8Error> {
+        std::str::from_utf8(&bytes)?;
+        Ok(StrView { bytes })
+    }
+
+    pub fn as_str(&self) -> &str {
+        // SAFETY: 构造时已经校验过 UTF-8，切片时也保证了落在字符边界上
+        unsafe { std::str::from_utf8_unchecked(&self.bytes) }
+    }
+
+    /// O(1) 子串。和 &str 的切片一样，索引不在字符边界上时 panic。
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> StrView {
+        let (start, end) = resolve_range(range, self.bytes.len());
+        let s = self.as_str();
+        assert!(
+            s.is_char_boundary(start) && s.is_char_boundary(end),
+            "byte range {}..{} is not on char boundaries",
+            start,
+            end
+        );
+        StrView {
+            bytes: self.bytes.slice(start..end),
+        }
+    }
+
+    /// 不 panic 的版本：索引越界或不在字符边界上时返回 None
+    pub fn get(&self, range: impl RangeBounds<usize> + Clone) -> Option<StrView> {
+        let s = self.as_str();
+        s.get((range.start_bound().cloned(), range.end_bound().cloned()))?;
+        Some(self.slice(range))
+    }
+
+    pub fn as_bytes(&self) -> &Bytes {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Bytes {
+        self.bytes
+    }
+}
+
+impl From<String> for StrView {
+    fn from(s: String) -> StrView {
+        StrView {
+            bytes: Bytes::from(s),
+        }
+    }
+}
+
+impl From<&str> for StrView {
+    fn from(s: &str) -> StrView {
+        StrView {
+            bytes: Bytes::from(s),
+        }
+    }
+}
+
+impl Deref for StrView {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for StrView {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for StrView {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for StrView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for StrView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone_and_slice_share_storage() {
+        let a = Bytes::from(String::from("hello world"));
+        let ptr = a.as_ptr();
+
+        // 对比 rust_str::test_string_clone：这里的 clone 不分配，地址也相同
+        let b = alloc_counter::assert_allocations!(0, { a.clone() });
+        assert_eq!(b.as_ptr(), ptr);
+
+        let world = alloc_counter::assert_allocations!(0, { a.slice(6..) });
+        assert_eq!(world, &b"world"[..]);
+        assert_eq!(world.as_ptr(), ptr.wrapping_add(6));
+        assert!(world.shares_storage(&a));
+        assert_eq!(a.ref_count(), 3);
+
+        // 切片的切片，区间是相对于当前切片的
+        assert_eq!(world.slice(1..=2), &b"or"[..]);
+    }
+
+    #[test]
+    fn test_from_vec_does_not_copy() {
+        let v = vec![1u8, 2, 3];
+        let ptr = v.as_ptr();
+        // 只有 Arc 的控制块需要分配，数据本身不拷贝
+        let b = alloc_counter::assert_allocations!(1, { Bytes::from(v) });
+        assert_eq!(b.as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_split_to() {
+        let mut b = Bytes::from("key=value");
+        let key = b.split_to(3);
+        assert_eq!(key, &b"key"[..]);
+        assert_eq!(b, &b"=value"[..]);
+        assert!(key.shares_storage(&b));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_slice_out_of_bounds() {
+        Bytes::from("abc").slice(1..10);
+    }
+
+    #[test]
+    fn test_bytes_mut_freeze() {
+        let mut buf = BytesMut::with_capacity(32);
+        buf.push_str("hello");
+        buf.push(b' ');
+        buf.extend_from_slice(b"world");
+        buf[0] = b'H'; // 冻结前可以原地修改
+        let ptr = buf.as_ptr();
+
+        let frozen = alloc_counter::assert_allocations!(1, { buf.freeze() });
+        assert_eq!(frozen, &b"Hello world"[..]);
+        assert_eq!(frozen.as_ptr(), ptr);
+        assert_eq!(format!("{:?}", frozen.slice(..5)), "b\"Hello\"");
+    }
+
+    #[test]
+    fn test_str_view() {
+        let s = StrView::from(String::from("你好, world"));
+        assert_eq!(s.len(), 13);
+        assert_eq!(s.slice(..3), "你");
+        assert!(s.slice(8..).starts_with("wor")); // Deref 到 str，可以直接用 str 的方法
+        assert!(s.slice(8..).as_bytes().shares_storage(s.as_bytes()));
+        assert_eq!(s.get(0..1), None); // '你' 占 3 个字节
+        assert_eq!(s.get(0..100), None);
+        assert_eq!(s.to_string(), "你好, world");
+    }
+
+    #[test]
+    #[should_panic(expected = "char boundaries")]
+    fn test_str_view_slice_inside_char() {
+        StrView::from("你好").slice(0..1);
+    }
+
+    #[test]
+    fn test_str_view_from_utf8() {
+        assert!(StrView::from_utf8(Bytes::from(vec![0xe4, 0xbd, 0xa0])).is_ok()); // "你"
+        assert!(StrView::from_utf8(Bytes::from(vec![0xe4, 0xbd])).is_err()); // 被截断
+    }
+}
@@ -65,7 +65,15 @@ pub fn concat_string(s1: &mut String, s2: String) -> String {
     s1.clone()
 }
 
+// rust_str 的文档按编号分段书写，不是 Markdown 列表
+#[allow(clippy::doc_lazy_continuation)]
 pub mod rust_str;
+pub mod bytes;
+
+// 测试时把计数分配器装成全局分配器，用来精确断言分配次数
+#[cfg(test)]
+#[global_allocator]
+static GLOBAL: alloc_counter::CountingAlloc = alloc_counter::CountingAlloc::system();
 
 #[cfg(test)]
 mod tests {
@@ -90,4 +98,21 @@ mod tests {
         assert_eq!(s3, "s1 + s2");
         assert_eq!(s1, s3);
     }
+
+    #[test]
+    fn test_concat_string_allocations() {
+        let mut s1 = String::from("s1");
+        let s2 = String::from("s2");
+
+        // 1. push_str(" + ")：容量 2 不够，扩容到 8 (u8 的最小非零容量)
+        // 2. push_str(&s2)：长度 7 <= 8，不分配
+        // 3. clone()：按长度分配 7 字节
+        // s2 在函数结束时被 drop，只释放，不分配
+        let (s3, stats) = alloc_counter::measure(|| concat_string(&mut s1, s2));
+        assert_eq!(s3, "s1 + s2");
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.reallocations, 1);
+        assert_eq!(stats.bytes_allocated, 8 + 7);
+        assert_eq!(stats.deallocations, 1);
+    }
 }
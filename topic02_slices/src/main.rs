@@ -60,8 +60,9 @@ fn handle_utf8_chars() {
     println!();
 
     // --- 解决方案 2: 获取第 N 个字符 ---
-    // 注意：这需要 O(N) 的时间复杂度，因为 UTF-8 是变长的
-    if let Some(c) = s.chars().nth(0) {
+    // 注意：nth(n) 需要 O(N) 的时间复杂度，因为 UTF-8 是变长的
+    // 取第 0 个字符时，nth(0) 等价于 next()
+    if let Some(c) = s.chars().next() {
         println!("First char: {}", c);
     }
 
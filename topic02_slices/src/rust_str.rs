@@ -199,7 +199,7 @@ fn test_string_move() {
     
     // 发生 Move：s1 的所有权移交给函数的参数 s，然后又移交给 s2
     // 此时 s1 已经失效，不能再访问
-    let s2 = take_ownership(s1); 
+    let s2 = alloc_counter::assert_allocations!(0, { take_ownership(s1) });
     
     let ptr_after = s2.as_ptr();
     
@@ -225,7 +225,7 @@ fn test_string_clone() {
     
     // 显式拷贝：s2 拥有全新的堆内存
     // 只有这样，才能在不转移所有权的情况下，让 s2 拥有数据
-    let s2 = s1.clone(); 
+    let s2 = alloc_counter::assert_bytes_allocated!(5, { s1.clone() });
     let ptr2 = s2.as_ptr();
     
     // 验证1：s1 依然有效 (没有被 Move)
@@ -0,0 +1,193 @@
+// String Arena: many &str borrows at once, while still appending data
+//
+// The problem with append_word:
+//
+// let mut s = String::from("a");
+// let r1 = append_word(&mut s, "b"); // r1 borrows from &mut s
+// let r2 = append_word(&mut s, "c"); // ❌ E0499: s is still mutably borrowed by r1
+// println!("{} {}", r1, r2);
+//
+// Why: pushing to a String can grow it, the heap buffer moves, and old &str would dangle.
+// So the compiler only allows "either one &mut or many &".
+//
+// The arena's approach:
+// - Data goes into preallocated chunks. A full chunk is left alone and a new one is
+//   started, so a chunk never grows and never moves.
+// - alloc_str only needs &self (a RefCell manages the chunk list), and the returned
+//   &str is tied to &self.
+// - So many &'arena str can exist at once, and we can keep allocating.
+// - When the Arena is dropped, all chunks are freed together (no per-string free).
+//
+// C++ Comparison:
+// Like std::pmr::monotonic_buffer_resource: allocate only, release everything on destruction.
+// The difference is that lifetimes guarantee the returned references can't outlive the Arena.
+
+use std::cell::{Cell, RefCell};
+
+const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+pub struct Arena {
+    // Each String is one chunk. We only push within its capacity, so its heap buffer never moves
+    chunks: RefCell<Vec<String>>,
+    chunk_size: usize,
+    count: Cell<usize>,
+}
+
+impl Default for Arena {
+    fn default() -> Arena {
+        Arena::new()
+    }
+}
+
+impl Arena {
+    pub fn new() -> Arena {
+        Arena::with_chunk_size(DEFAULT_CHUNK_SIZE)
+    }
+
+    /// Sets the chunk size in bytes. A string larger than that gets a chunk of its own, sized to fit.
+    pub fn with_chunk_size(chunk_size: usize) -> Arena {
+        Arena {
+            chunks: RefCell::new(Vec::new()),
+            chunk_size: chunk_size.max(1),
+            count: Cell::new(0),
+        }
+    }
+
+    /// Copies `s` into the Arena and returns a &str that lives as long as the Arena.
+    ///
+    /// Note the signature: only &self, so it can be called while other &str are still held.
+    pub fn alloc_str(&self, s: &str) -> &str {
+        let mut chunks = self.chunks.borrow_mut();
+
+        let fits = chunks
+            .last()
+            .is_some_and(|c| c.capacity() - c.len() >= s.len());
+        if !fits {
+            chunks.push(String::with_capacity(self.chunk_size.max(s.len())));
+        }
+
+        let chunk = chunks.last_mut().expect("just ensured a chunk exists");
+        let start = chunk.len();
+        // There is enough capacity: push_str won't reallocate, existing data stays where it is
+        chunk.push_str(s);
+        let ptr = chunk[start..].as_ptr();
+        self.count.set(self.count.get() + 1);
+
+        // SAFETY:
+        // 1. ptr..ptr+len was just written and is valid UTF-8 (copied from a &str).
+        // 2. Chunks only grow within their capacity, so the heap buffer never moves, and
+        //    bytes that were written are never modified again.
+        // 3. Chunks are only freed when the Arena drops, and the return value's lifetime
+        //    is tied to &self, so the compiler makes sure it can't outlive the Arena.
+        // 4. When the chunks Vec grows it only moves the String structs (ptr/len/cap),
+        //    not the heap data.
+        unsafe { std::str::from_utf8_unchecked(std::slice::from_raw_parts(ptr, s.len())) }
+    }
+
+    /// Number of strings allocated
+    pub fn len(&self) -> usize {
+        self.count.get()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of bytes used
+    pub fn allocated_bytes(&self) -> usize {
+        self.chunks.borrow().iter().map(String::len).sum()
+    }
+
+    /// Number of chunks (one heap allocation each)
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.borrow().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_many_borrows_while_allocating() {
+        let arena = Arena::new();
+
+        // With String + append_word, this is exactly what doesn't compile:
+        // allocating again while r1 is held. With the Arena it's fine.
+        let r1 = arena.alloc_str("hello");
+        let r2 = arena.alloc_str("world");
+        let r3 = arena.alloc_str(&format!("{} {}", r1, r2));
+        assert_eq!((r1, r2, r3), ("hello", "world", "hello world"));
+        assert_eq!(arena.len(), 3);
+        assert_eq!(arena.allocated_bytes(), 5 + 5 + 11);
+    }
+
+    #[test]
+    fn test_store_slices_in_struct() {
+        // A struct borrowing from the Arena: every field is a &'a str, usable alongside the Arena
+        struct Person<'a> {
+            name: &'a str,
+            city: &'a str,
+        }
+
+        let arena = Arena::new();
+        let mut people = Vec::new();
+        for (name, city) in [("Alice", "Paris"), ("Bob", "Beijing")] {
+            people.push(Person {
+                name: arena.alloc_str(name),
+                city: arena.alloc_str(city),
+            });
+        }
+        // The earlier &str are still in use while we keep allocating
+        let extra = arena.alloc_str("Carol");
+        assert_eq!(people[0].name, "Alice");
+        assert_eq!(people[1].city, "Beijing");
+        assert_eq!(extra, "Carol");
+    }
+
+    #[test]
+    fn test_chunks_never_move() {
+        let arena = Arena::with_chunk_size(8);
+        let a = arena.alloc_str("abcd");
+        let a_ptr = a.as_ptr();
+        let b = arena.alloc_str("efgh"); // exactly fills the first chunk
+        let c = arena.alloc_str("ijk"); // new chunk
+        let big = arena.alloc_str("a string longer than eight bytes"); // gets a chunk of its own
+
+        assert_eq!(arena.chunk_count(), 3);
+        assert_eq!(a.as_ptr(), a_ptr);
+        // a and b sit next to each other in the same chunk
+        assert_eq!(b.as_ptr(), a_ptr.wrapping_add(4));
+        assert_eq!((a, b, c), ("abcd", "efgh", "ijk"));
+        assert_eq!(big.len(), 32);
+    }
+
+    #[test]
+    fn test_one_allocation_per_chunk() {
+        let arena = Arena::new();
+        let words: Vec<String> = (0..100).map(|i| format!("word{}", i)).collect();
+
+        // First alloc: 1 for the chunk list Vec + 1 for the first chunk
+        alloc_counter::assert_allocations!(2, { arena.alloc_str(&words[0]) });
+        // The other 99 strings all land in the same 4KB chunk: 0 allocations
+        alloc_counter::assert_allocations!(0, {
+            for w in &words[1..] {
+                arena.alloc_str(w);
+            }
+        });
+        assert_eq!(arena.chunk_count(), 1);
+
+        // Dropping frees in bulk: once per chunk, plus the chunk list itself
+        let (_, stats) = alloc_counter::measure(|| drop(arena));
+        assert_eq!(stats.deallocations, 2);
+    }
+
+    #[test]
+    fn test_empty_and_unicode() {
+        let arena = Arena::with_chunk_size(4);
+        assert!(arena.is_empty());
+        assert_eq!(arena.alloc_str(""), "");
+        assert_eq!(arena.alloc_str("你好"), "你好");
+        assert_eq!(arena.len(), 2);
+    }
+}
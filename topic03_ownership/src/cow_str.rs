@@ -0,0 +1,212 @@
+// Copy-on-Write string transforms
+//
+// `modify_string` always takes ownership, `append_word` always mutates in place.
+// But for most inputs, many transforms have nothing to do:
+// - Trimming: the result is always a slice of the original string.
+// - HTML escaping: a new string is only needed if `<`, `&`, ... appear.
+// - Replacing: a new string is only needed if the pattern is found.
+//
+// Cow<'a, str> (Clone-on-Write) expresses exactly this "maybe borrowed, maybe owned":
+//
+// enum Cow<'a, str> {
+//     Borrowed(&'a str), // unchanged: borrow the input, zero allocations
+//     Owned(String),     // changed: allocate a new String
+// }
+//
+// C++ Comparison:
+// Like returning std::variant<std::string_view, std::string>,
+// but lifetimes guarantee the Borrowed branch can't dangle.
+
+use std::borrow::Cow;
+
+/// Strips leading and trailing whitespace.
+/// The result is always a subslice of the input: always Borrowed, never allocates.
+pub fn trim(s: &str) -> Cow<'_, str> {
+    Cow::Borrowed(s.trim())
+}
+
+/// Escapes the HTML special characters (`& < > " '`).
+/// Borrows the input if there are none; otherwise allocates once, sized for the escaped length.
+pub fn escape_html(s: &str) -> Cow<'_, str> {
+    let needs_escape = |c: char| matches!(c, '&' | '<' | '>' | '"' | '\'');
+
+    // Find the first character to escape; everything before it is copied in one go
+    let first = match s.find(needs_escape) {
+        None => return Cow::Borrowed(s),
+        Some(i) => i,
+    };
+
+    // Compute the exact escaped length up front so the result is allocated once
+    let extra: usize = s[first..]
+        .chars()
+        .map(|c| match c {
+            '&' => 4,
+            '<' | '>' => 3,
+            '"' => 5,
+            '\'' => 4,
+            _ => 0,
+        })
+        .sum();
+    let mut out = String::with_capacity(s.len() + extra);
+    out.push_str(&s[..first]);
+    for c in s[first..].chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    Cow::Owned(out)
+}
+
+/// Replaces every `from` with `to`, like `str::replace`.
+/// Borrows the input if `from` is not found. An empty `from` matches at every
+/// char boundary, as in std, so it always produces an Owned result.
+pub fn replace<'a>(s: &'a str, from: &str, to: &str) -> Cow<'a, str> {
+    if !s.contains(from) {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(s.replace(from, to))
+    }
+}
+
+/// Trims the ends and collapses every inner run of whitespace into a single space.
+/// If the inner whitespace is already single spaces, the result is a subslice (Borrowed).
+pub fn normalize_whitespace(s: &str) -> Cow<'_, str> {
+    let trimmed = s.trim();
+
+    // Already normal: no runs of whitespace, and no whitespace other than ' '
+    let mut prev_space = false;
+    let is_normalized = trimmed.chars().all(|c| {
+        let ok = !c.is_whitespace() || (c == ' ' && !prev_space);
+        prev_space = c.is_whitespace();
+        ok
+    });
+    if is_normalized {
+        return Cow::Borrowed(trimmed);
+    }
+
+    let mut out = String::with_capacity(trimmed.len());
+    for word in trimmed.split_whitespace() {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(word);
+    }
+    Cow::Owned(out)
+}
+
+/// Cow version of `modify_string`: makes sure the string ends with `suffix`.
+/// Doesn't allocate if it already does, and the caller never gives up ownership.
+pub fn ensure_suffix<'a>(s: &'a str, suffix: &str) -> Cow<'a, str> {
+    if s.ends_with(suffix) {
+        Cow::Borrowed(s)
+    } else {
+        let mut out = String::with_capacity(s.len() + suffix.len());
+        out.push_str(s);
+        out.push_str(suffix);
+        Cow::Owned(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc_counter::measure;
+
+    #[test]
+    fn test_trim_never_allocates() {
+        let (r, stats) = measure(|| trim("  hello  "));
+        assert_eq!(r, "hello");
+        assert!(matches!(r, Cow::Borrowed(_)));
+        assert_eq!(stats.allocations, 0);
+    }
+
+    #[test]
+    fn test_escape_html() {
+        let (r, stats) = measure(|| escape_html("plain text"));
+        assert_eq!(r, "plain text");
+        assert!(matches!(r, Cow::Borrowed(_)));
+        assert_eq!(stats.allocations, 0);
+
+        let (r, stats) = measure(|| escape_html("a < b && \"c\" > 'd'"));
+        assert_eq!(r, "a &lt; b &amp;&amp; &quot;c&quot; &gt; &#39;d&#39;");
+        assert!(!matches!(r, Cow::Borrowed(_)));
+        // Capacity is reserved for the escaped length, so no reallocation
+        assert_eq!(stats.allocations, 1);
+
+        let (r, stats) = measure(|| escape_html("你好 <br>"));
+        assert_eq!(r, "你好 &lt;br&gt;");
+        assert_eq!(stats.allocations, 1);
+    }
+
+    #[test]
+    fn test_replace() {
+        let (r, stats) = measure(|| replace("hello world", "xyz", "abc"));
+        assert_eq!(r, "hello world");
+        assert!(matches!(r, Cow::Borrowed(_)));
+        assert_eq!(stats.allocations, 0);
+
+        let (r, stats) = measure(|| replace("hello world", "o", "0"));
+        assert_eq!(r, "hell0 w0rld");
+        assert!(!matches!(r, Cow::Borrowed(_)));
+        // How often str::replace allocates is up to std; the Borrowed path above is ours
+        assert!(stats.allocations <= 1);
+
+        // An empty pattern matches at every char boundary, same as str::replace
+        assert_eq!(replace("abc", "", "x"), "abc".replace("", "x"));
+        assert_eq!(replace("abc", "", "x"), "xaxbxcx");
+        assert!(!matches!(replace("abc", "", "x"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_normalize_whitespace() {
+        let (r, stats) = measure(|| normalize_whitespace("  already normal  "));
+        assert_eq!(r, "already normal");
+        assert!(matches!(r, Cow::Borrowed(_)));
+        assert_eq!(stats.allocations, 0);
+
+        let (r, stats) = measure(|| normalize_whitespace("a  b\t\tc\n d"));
+        assert_eq!(r, "a b c d");
+        assert!(!matches!(r, Cow::Borrowed(_)));
+        assert_eq!(stats.allocations, 1);
+
+        assert_eq!(normalize_whitespace("   "), "");
+        assert!(!matches!(normalize_whitespace("a\tb"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_ensure_suffix_vs_modify_string() {
+        let (r, stats) = measure(|| ensure_suffix("Hello World", " World"));
+        assert_eq!(r, "Hello World");
+        assert_eq!(stats.allocations, 0);
+
+        let (r, stats) = measure(|| ensure_suffix("Hello", " World"));
+        assert_eq!(r, "Hello World");
+        assert_eq!(stats.allocations, 1);
+
+        // Compare: modify_string takes ownership and push_str's into the same buffer.
+        // "Hello" has a capacity of 5, so it has to grow once
+        let hello = String::from("Hello");
+        let (r, stats) = measure(|| crate::modify_string(hello));
+        assert_eq!(r, "Hello World");
+        assert_eq!(stats.allocations, 1);
+    }
+
+    #[test]
+    fn test_into_owned_only_copies_borrowed() {
+        // Borrowed -> into_owned copies once; Owned -> into_owned moves out without allocating
+        let owned = replace("aaa", "a", "b");
+        let (s, stats) = measure(|| owned.into_owned());
+        assert_eq!(s, "bbb");
+        assert_eq!(stats.allocations, 0);
+
+        let borrowed = replace("aaa", "x", "b");
+        let (s, stats) = measure(|| borrowed.into_owned());
+        assert_eq!(s, "aaa");
+        assert_eq!(stats.allocations, 1);
+    }
+}
@@ -0,0 +1,525 @@
+// Editing Pipeline
+//
+// `append_word` and `upper_word` each do one thing.
+// EditPipeline collects several edits and applies them to a &mut String in one pass:
+//
+// let mut s = String::from("hello world");
+// let undo = EditPipeline::new()
+//     .uppercase_range(0..5)     // like upper_word, but only on a range
+//     .replace(6..11, "rust")
+//     .append("!")               // like append_word
+//     .apply(&mut s)?;           // "HELLO rust!"
+// undo.undo(&mut s)?;            // back to "hello world"
+//
+// Every position is a byte index into the ORIGINAL string and must be on a char
+// boundary (same rule as slicing). Edits may not overlap, so the order they are
+// added in doesn't matter, except for insertions at the same position: those keep it.
+//
+// apply() first checks every edit without touching `s`, then copies the untouched
+// parts and the new text from left to right into one buffer of exactly the final
+// size. Every byte is copied once: O(len + new text), however many edits there are.
+// Only Undo needs to keep copies of the text that was removed or overwritten.
+
+use std::fmt;
+use std::ops::Range;
+
+/// A single edit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    /// Inserts text at byte position `at`
+    Insert { at: usize, text: String },
+    /// Appends text at the end
+    Append { text: String },
+    /// Deletes a range
+    Delete { range: Range<usize> },
+    /// Replaces a range with new text
+    Replace { range: Range<usize>, text: String },
+    /// Uppercases the ASCII letters in a range (the length doesn't change)
+    UppercaseRange { range: Range<usize> },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    /// The index is past the end of the string
+    OutOfBounds { index: usize, len: usize },
+    /// The index falls inside a multi-byte character
+    NotCharBoundary { index: usize },
+    /// start > end
+    InvalidRange { start: usize, end: usize },
+    /// Two edits in the same pipeline touch the same bytes
+    Overlapping {
+        first: Range<usize>,
+        second: Range<usize>,
+    },
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditError::OutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds (len {})", index, len)
+            }
+            EditError::NotCharBoundary { index } => {
+                write!(f, "byte index {} is not a char boundary", index)
+            }
+            EditError::InvalidRange { start, end } => {
+                write!(f, "invalid range {}..{}", start, end)
+            }
+            EditError::Overlapping { first, second } => {
+                write!(
+                    f,
+                    "edits at {}..{} and {}..{} overlap",
+                    first.start, first.end, second.start, second.end
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+fn check_index(s: &str, index: usize) -> Result<(), EditError> {
+    if index > s.len() {
+        Err(EditError::OutOfBounds {
+            index,
+            len: s.len(),
+        })
+    } else if !s.is_char_boundary(index) {
+        Err(EditError::NotCharBoundary { index })
+    } else {
+        Ok(())
+    }
+}
+
+fn check_range(s: &str, range: &Range<usize>) -> Result<(), EditError> {
+    if range.start > range.end {
+        return Err(EditError::InvalidRange {
+            start: range.start,
+            end: range.end,
+        });
+    }
+    check_index(s, range.start)?;
+    check_index(s, range.end)
+}
+
+impl Edit {
+    /// The bytes of the original string this edit covers (empty for insertions)
+    fn target(&self, s: &str) -> Range<usize> {
+        match self {
+            Edit::Insert { at, .. } => *at..*at,
+            Edit::Append { .. } => s.len()..s.len(),
+            Edit::Delete { range }
+            | Edit::Replace { range, .. }
+            | Edit::UppercaseRange { range } => range.clone(),
+        }
+    }
+
+    // Order inside a pipeline: by position, appends last
+    fn sort_key(&self) -> (usize, usize) {
+        match self {
+            Edit::Append { .. } => (usize::MAX, usize::MAX),
+            Edit::Insert { at, .. } => (*at, *at),
+            Edit::Delete { range }
+            | Edit::Replace { range, .. }
+            | Edit::UppercaseRange { range } => (range.start, range.end),
+        }
+    }
+
+    /// Applies this single edit in place and returns the edit that undoes it
+    pub fn apply(&self, s: &mut String) -> Result<Edit, EditError> {
+        match self {
+            Edit::Insert { at, text } => {
+                check_index(s, *at)?;
+                s.insert_str(*at, text);
+                Ok(Edit::Delete {
+                    range: *at..*at + text.len(),
+                })
+            }
+            Edit::Append { text } => {
+                let start = s.len();
+                s.push_str(text);
+                Ok(Edit::Delete {
+                    range: start..s.len(),
+                })
+            }
+            Edit::Delete { range } => {
+                check_range(s, range)?;
+                // drain hands back the removed text, which is exactly what the inverse needs
+                let removed: String = s.drain(range.clone()).collect();
+                Ok(Edit::Insert {
+                    at: range.start,
+                    text: removed,
+                })
+            }
+            Edit::Replace { range, text } => {
+                check_range(s, range)?;
+                let original = String::from(&s[range.clone()]);
+                s.replace_range(range.clone(), text);
+                Ok(Edit::Replace {
+                    range: range.start..range.start + text.len(),
+                    text: original,
+                })
+            }
+            Edit::UppercaseRange { range } => {
+                check_range(s, range)?;
+                let original = String::from(&s[range.clone()]);
+                // Same as upper_word: ASCII only, the byte length doesn't change
+                s[range.clone()].make_ascii_uppercase();
+                Ok(Edit::Replace {
+                    range: range.clone(),
+                    text: original,
+                })
+            }
+        }
+    }
+}
+
+/// Undo handle: the inverse edits, addressed against the edited string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Undo {
+    inverse: Vec<Edit>,
+}
+
+impl Undo {
+    /// The inverse edits, sorted by position
+    pub fn edits(&self) -> &[Edit] {
+        &self.inverse
+    }
+
+    /// Undoes the edits and returns a "redo" handle
+    pub fn undo(self, s: &mut String) -> Result<Undo, EditError> {
+        apply_all(&self.inverse, s)
+    }
+}
+
+/// A chainable editing pipeline
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditPipeline {
+    edits: Vec<Edit>,
+}
+
+impl EditPipeline {
+    pub fn new() -> EditPipeline {
+        EditPipeline::default()
+    }
+
+    /// Adds an edit. The list stays sorted by position, so apply() never has to sort;
+    /// among edits at the same position the earlier one stays first.
+    pub fn push(mut self, edit: Edit) -> EditPipeline {
+        let key = edit.sort_key();
+        let at = self.edits.partition_point(|e| e.sort_key() <= key);
+        self.edits.insert(at, edit);
+        self
+    }
+
+    pub fn insert(self, at: usize, text: &str) -> EditPipeline {
+        self.push(Edit::Insert {
+            at,
+            text: String::from(text),
+        })
+    }
+
+    pub fn append(self, text: &str) -> EditPipeline {
+        self.push(Edit::Append {
+            text: String::from(text),
+        })
+    }
+
+    pub fn delete(self, range: Range<usize>) -> EditPipeline {
+        self.push(Edit::Delete { range })
+    }
+
+    pub fn replace(self, range: Range<usize>, text: &str) -> EditPipeline {
+        self.push(Edit::Replace {
+            range,
+            text: String::from(text),
+        })
+    }
+
+    pub fn uppercase_range(self, range: Range<usize>) -> EditPipeline {
+        self.push(Edit::UppercaseRange { range })
+    }
+
+    /// The edits sorted by position, the order apply() goes through them
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+
+    /// Applies all edits in one pass. Positions refer to `s` as it is now.
+    ///
+    /// Every edit is checked before anything is written: on error `s` is untouched.
+    pub fn apply(&self, s: &mut String) -> Result<Undo, EditError> {
+        apply_all(&self.edits, s)
+    }
+}
+
+// `edits` must be sorted by position, as EditPipeline and Undo keep them
+fn apply_all(edits: &[Edit], s: &mut String) -> Result<Undo, EditError> {
+    // Check everything against the original string before writing anything
+    let mut prev = 0..0;
+    let mut new_len = s.len();
+    for edit in edits {
+        let range = edit.target(s);
+        check_range(s, &range)?;
+        if range.start < prev.end {
+            return Err(EditError::Overlapping {
+                first: prev,
+                second: range,
+            });
+        }
+        new_len -= range.len();
+        new_len += match edit {
+            Edit::Insert { text, .. } | Edit::Append { text } | Edit::Replace { text, .. } => {
+                text.len()
+            }
+            Edit::Delete { .. } => 0,
+            Edit::UppercaseRange { range } => range.len(),
+        };
+        prev = range;
+    }
+
+    // Splice once, left to right: untouched text, new text, untouched text, ...
+    let mut out = String::with_capacity(new_len);
+    let mut inverse = Vec::with_capacity(edits.len());
+    let mut copied = 0;
+    for edit in edits {
+        let range = edit.target(s);
+        out.push_str(&s[copied..range.start]);
+        let start = out.len();
+        let original = &s[range.clone()];
+        let inv = match edit {
+            Edit::Insert { text, .. } | Edit::Append { text } => {
+                out.push_str(text);
+                Edit::Delete {
+                    range: start..out.len(),
+                }
+            }
+            Edit::Delete { .. } => Edit::Insert {
+                at: start,
+                text: String::from(original),
+            },
+            Edit::Replace { text, .. } => {
+                out.push_str(text);
+                Edit::Replace {
+                    range: start..out.len(),
+                    text: String::from(original),
+                }
+            }
+            Edit::UppercaseRange { .. } => {
+                out.push_str(original);
+                out[start..].make_ascii_uppercase();
+                Edit::Replace {
+                    range: start..out.len(),
+                    text: String::from(original),
+                }
+            }
+        };
+        inverse.push(inv);
+        copied = range.end;
+    }
+    out.push_str(&s[copied..]);
+    *s = out;
+    Ok(Undo { inverse })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chain_and_undo() {
+        let mut s = String::from("hello world");
+        let undo = EditPipeline::new()
+            .append("!")
+            .uppercase_range(0..5)
+            .replace(6..11, "rust")
+            .insert(0, ">> ")
+            .delete(5..6)
+            .apply(&mut s)
+            .unwrap();
+        assert_eq!(s, ">> HELLOrust!");
+
+        let redo = undo.undo(&mut s).unwrap();
+        assert_eq!(s, "hello world");
+
+        // Undoing the undo = redo
+        redo.undo(&mut s).unwrap();
+        assert_eq!(s, ">> HELLOrust!");
+    }
+
+    #[test]
+    fn test_positions_refer_to_original() {
+        // 0..5 is "hello" no matter what is inserted in front of it
+        let mut a = String::from("hello world");
+        EditPipeline::new()
+            .insert(0, "say ")
+            .uppercase_range(0..5)
+            .apply(&mut a)
+            .unwrap();
+        assert_eq!(a, "say HELLO world");
+
+        let mut b = String::from("hello world");
+        EditPipeline::new()
+            .uppercase_range(0..5)
+            .insert(0, "say ")
+            .apply(&mut b)
+            .unwrap();
+        assert_eq!(b, a);
+
+        // Only insertions at the same position keep the order they were added in
+        let mut c = String::from("b");
+        EditPipeline::new()
+            .append("c")
+            .insert(0, "a")
+            .insert(0, "-")
+            .insert(1, "d")
+            .apply(&mut c)
+            .unwrap();
+        assert_eq!(c, "a-bdc");
+    }
+
+    #[test]
+    fn test_overlapping_edits() {
+        let mut s = String::from("hello world");
+        let err = EditPipeline::new()
+            .delete(0..5)
+            .uppercase_range(4..7)
+            .apply(&mut s)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            EditError::Overlapping {
+                first: 0..5,
+                second: 4..7
+            }
+        );
+        assert_eq!(err.to_string(), "edits at 0..5 and 4..7 overlap");
+
+        // An insertion strictly inside a replaced range overlaps it, one at its edge doesn't
+        let err = EditPipeline::new()
+            .replace(0..5, "x")
+            .insert(2, "y")
+            .apply(&mut s)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            EditError::Overlapping {
+                first: 0..5,
+                second: 2..2
+            }
+        );
+        assert_eq!(s, "hello world");
+
+        EditPipeline::new()
+            .replace(0..5, "bye")
+            .insert(5, ",")
+            .insert(0, "oh ")
+            .apply(&mut s)
+            .unwrap();
+        assert_eq!(s, "oh bye, world");
+    }
+
+    #[test]
+    fn test_same_as_append_and_upper_word() {
+        let mut a = String::from("Hello");
+        crate::upper_word(&mut a);
+        crate::append_word(&mut a, "zhr");
+
+        let mut b = String::from("Hello");
+        EditPipeline::new()
+            .uppercase_range(0..5)
+            .append("zhr")
+            .apply(&mut b)
+            .unwrap();
+
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_errors_leave_string_untouched() {
+        let mut s = String::from("你好 world");
+        let original = s.clone();
+
+        // '你' is 3 bytes, index 1 falls inside it
+        let err = EditPipeline::new()
+            .append("!")
+            .delete(1..3)
+            .apply(&mut s)
+            .unwrap_err();
+        assert_eq!(err, EditError::NotCharBoundary { index: 1 });
+        assert_eq!(s, original);
+
+        let err = EditPipeline::new()
+            .insert(100, "x")
+            .apply(&mut s)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            EditError::OutOfBounds {
+                index: 100,
+                len: 12
+            }
+        );
+
+        let reversed = Range { start: 5, end: 2 };
+        let err = EditPipeline::new()
+            .delete(reversed)
+            .apply(&mut s)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "invalid range 5..2");
+        assert_eq!(s, original);
+    }
+
+    #[test]
+    fn test_uppercase_only_ascii() {
+        let mut s = String::from("你好 world");
+        let undo = EditPipeline::new()
+            .uppercase_range(0..s.len())
+            .apply(&mut s)
+            .unwrap();
+        assert_eq!(s, "你好 WORLD");
+        assert_eq!(
+            undo.edits(),
+            &[Edit::Replace {
+                range: 0..12,
+                text: String::from("你好 world"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_single_edit_in_place() {
+        let mut s = String::from("hello");
+        let inverse = Edit::Insert {
+            at: 5,
+            text: String::from("!"),
+        }
+        .apply(&mut s)
+        .unwrap();
+        assert_eq!(s, "hello!");
+        inverse.apply(&mut s).unwrap();
+        assert_eq!(s, "hello");
+    }
+
+    #[test]
+    fn test_no_intermediate_allocations() {
+        let s = String::from("hello world");
+        let pipeline = EditPipeline::new().insert(0, "[").append("]").delete(0..1);
+
+        // The result buffer, sized exactly, plus what Undo keeps:
+        // the inverse list and the deleted text
+        let mut edited = s.clone();
+        alloc_counter::assert_allocations!(3, { pipeline.apply(&mut edited).unwrap() });
+        assert_eq!(edited, "[ello world]");
+        assert_eq!(edited.capacity(), edited.len());
+
+        // Many edits still go through the string once: one buffer, no growth
+        let mut pipeline = EditPipeline::new();
+        for i in 0..s.len() {
+            pipeline = pipeline.insert(i, "-");
+        }
+        let mut edited = s.clone();
+        alloc_counter::assert_allocations!(2, { pipeline.apply(&mut edited).unwrap() });
+        assert_eq!(edited, "-h-e-l-l-o- -w-o-r-l-d");
+    }
+}
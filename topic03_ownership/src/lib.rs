@@ -25,14 +25,26 @@ pub fn modify_string(s: String) -> String {
 // Actually, let's make it a bit harder.
 // Function that takes ownership, modifies, and returns it (Move semantics).
 pub fn append_word<'a>(s: &'a mut String, s2:&'a str) -> &'a str {
-    s.push_str(&s2);
+    s.push_str(s2);
     s
 }
 
+// The signature is part of the exercise, keep &mut String (clippy suggests &mut str)
+#[allow(clippy::ptr_arg)]
 pub fn upper_word(s: &mut String) {
     s.make_ascii_uppercase();
 }
 
+pub mod cow_str;
+pub mod traced;
+pub mod edit;
+pub mod arena;
+
+// In tests, install the counting allocator globally so we can assert how often each function allocates
+#[cfg(test)]
+#[global_allocator]
+static GLOBAL: alloc_counter::CountingAlloc = alloc_counter::CountingAlloc::system();
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,12 +57,42 @@ mod tests {
 
         let s3 = append_word(&mut s, "zhr");
 
-        // // success
-        // assert_eq!(s3, "HELLOzhr");
+        // success: s can only be read again once s3 (the continued mutable borrow) is done
+        assert_eq!(s3, "HELLOzhr");
+        assert_eq!(s, "HELLOzhr");
+
+        // fail: reading s before using s3 is E0502 (s is still mutably borrowed by s3)
+        // see compile_fail/cases/e0502_append_word.rs
         // assert_eq!(s, "HELLOzhr");
+        // assert_eq!(s3, "HELLOzhr");
+    }
 
-        // fail
-        assert_eq!(s, "HELLOzhr");
-        assert_eq!(s3, "HELLOzhr");
+    #[test]
+    fn test_modify_string_allocations() {
+        // "Hello" has a capacity of exactly 5, appending " World" has to grow (realloc) once
+        let s = String::from("Hello");
+        let s = alloc_counter::assert_allocations!(1, { modify_string(s) });
+        assert_eq!(s, "Hello World");
+
+        // Reserve capacity up front: ownership moves in and out, but nothing is allocated
+        let mut s = String::with_capacity(16);
+        s.push_str("Hello");
+        let s = alloc_counter::assert_allocations!(0, { modify_string(s) });
+        assert_eq!(s, "Hello World");
+    }
+
+    #[test]
+    fn test_append_word_allocations() {
+        let mut s = String::from("HELLO");
+        // Growing requests max(2 * cap, needed length), 10 bytes here
+        let ret = alloc_counter::assert_bytes_allocated!(10, { append_word(&mut s, "zhr") });
+        assert_eq!(ret, "HELLOzhr");
+
+        // 2 bytes of capacity left, so appending 2 more doesn't allocate; the returned &str is just a borrow
+        let ret = alloc_counter::assert_allocations!(0, { append_word(&mut s, "!!") });
+        assert_eq!(ret, "HELLOzhr!!");
+
+        // upper_word works in place and never allocates
+        alloc_counter::assert_allocations!(0, { upper_word(&mut s) });
     }
 }
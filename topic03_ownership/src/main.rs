@@ -12,6 +12,8 @@
 //    - You can have EITHER one mutable reference OR many immutable references.
 //    - References must be valid (no dangling pointers).
 
+use topic03_ownership::traced::Tracer;
+
 fn main() {
     println!("--- Move Semantics ---");
     let s1 = String::from("hello");
@@ -39,8 +41,21 @@ fn main() {
     let r2 = &s4;
     // let r3 = &mut s4; // Error: cannot borrow as mutable because it is also borrowed as immutable
     println!("r1: {}, r2: {}", r1, r2);
+
+    println!("\n--- Tracing Ownership ---");
+    // Traced<T> records the moments above, which comments can only describe, as a timeline
+    let tracer = Tracer::new();
+    {
+        let t1 = tracer.track("t1", String::from("hello"));
+        let t2 = t1.clone(); // Clone: new id
+        let t3 = t1.moved_to("t3"); // Move: can only be marked explicitly
+        println!("t2 = {}, t3 = {}", *t2.borrow(), *t3.borrow());
+    } // t3, t2 are dropped in reverse declaration order
+    print!("{}", tracer.dump());
 }
 
+// The signature is part of the lesson, keep &String (clippy suggests &str)
+#[allow(clippy::ptr_arg)]
 fn calculate_length(s: &String) -> usize {
     s.len()
 } // s goes out of scope, but it's just a reference, so nothing happens.
@@ -0,0 +1,382 @@
+// Ownership Tracing: recording the "life" of a value
+//
+// main.rs can only explain Move / Clone / Drop in comments.
+// Traced<T> turns those moments into a timeline that can be printed:
+//
+// let tracer = Tracer::new();
+// let a = tracer.track("a", String::from("hello")); // #1 created
+// let b = a.clone();                                // #2 cloned from #1
+// {
+//     let r = a.borrow();                           // #1 borrowed
+// }                                                 // #1 released
+// drop(a);                                          // #1 dropped
+// println!("{}", tracer.dump());
+//
+// Note: a Move in Rust is just a bitwise copy (memcpy) with no hook to intercept it.
+// That is the biggest difference from C++ move constructors.
+// So moves have to be marked explicitly: `let b = a.moved_to("b");`
+//
+// C++ Comparison:
+// Like logging in the copy constructor and destructor to trace object lifetimes.
+// The difference is that Rust borrows can be recorded too, through RAII guards.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+/// What happened to a value
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventKind {
+    /// Created through `Tracer::track`
+    Created,
+    /// Deep-copied from another value (id) through `clone()`
+    Cloned { from: u64 },
+    /// Ownership transfer, marked explicitly
+    Moved { to: String },
+    /// An immutable borrow guard was taken
+    Borrowed,
+    /// A mutable borrow guard was taken
+    BorrowedMut,
+    /// The borrow guard was dropped, the borrow is over
+    Released,
+    /// The inner value was handed out through `into_inner` (no Dropped follows)
+    Unwrapped,
+    /// The value was destroyed
+    Dropped,
+}
+
+/// One entry in the timeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    /// Position in the timeline, counting up from 0; it orders the events.
+    /// Restarts at 0 after `Tracer::clear()` (ids don't).
+    pub seq: usize,
+    /// Unique id of the value (a clone gets a new id)
+    pub id: u64,
+    pub label: Rc<str>,
+    pub kind: EventKind,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:>3}] {}#{} ", self.seq, self.label, self.id)?;
+        match &self.kind {
+            EventKind::Created => write!(f, "created"),
+            EventKind::Cloned { from } => write!(f, "cloned from #{}", from),
+            EventKind::Moved { to } => write!(f, "moved to {}", to),
+            EventKind::Borrowed => write!(f, "borrowed (&)"),
+            EventKind::BorrowedMut => write!(f, "borrowed (&mut)"),
+            EventKind::Released => write!(f, "borrow released"),
+            EventKind::Unwrapped => write!(f, "unwrapped (into_inner)"),
+            EventKind::Dropped => write!(f, "dropped"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct TraceLog {
+    next_id: u64,
+    events: Vec<Event>,
+}
+
+/// The event log. Cloned Tracers share the same log (Rc<RefCell<..>>).
+#[derive(Clone, Default)]
+pub struct Tracer {
+    log: Rc<RefCell<TraceLog>>,
+}
+
+impl Tracer {
+    pub fn new() -> Tracer {
+        Tracer::default()
+    }
+
+    /// Starts tracking a value and records Created
+    pub fn track<T>(&self, label: &str, value: T) -> Traced<T> {
+        self.register(Rc::from(label), value, EventKind::Created)
+    }
+
+    fn register<T>(&self, label: Rc<str>, value: T, kind: EventKind) -> Traced<T> {
+        let id = {
+            let mut log = self.log.borrow_mut();
+            log.next_id += 1;
+            log.next_id
+        };
+        let traced = Traced {
+            value: Some(value),
+            id,
+            label,
+            tracer: self.clone(),
+        };
+        traced.record(kind);
+        traced
+    }
+
+    fn record(&self, id: u64, label: &Rc<str>, kind: EventKind) {
+        let mut log = self.log.borrow_mut();
+        let seq = log.events.len();
+        log.events.push(Event {
+            seq,
+            id,
+            label: Rc::clone(label),
+            kind,
+        });
+    }
+
+    /// A snapshot of all events
+    pub fn events(&self) -> Vec<Event> {
+        self.log.borrow().events.clone()
+    }
+
+    /// All events of one value (id)
+    pub fn events_for(&self, id: u64) -> Vec<Event> {
+        self.log
+            .borrow()
+            .events
+            .iter()
+            .filter(|e| e.id == id)
+            .cloned()
+            .collect()
+    }
+
+    /// Clears the events. Sequence numbers restart at 0; ids keep counting up and never repeat.
+    pub fn clear(&self) {
+        self.log.borrow_mut().events.clear();
+    }
+
+    /// The timeline as multi-line text, handy to print while debugging
+    pub fn dump(&self) -> String {
+        let log = self.log.borrow();
+        let mut out = String::new();
+        for e in &log.events {
+            out.push_str(&e.to_string());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// A tracked value.
+///
+/// The inner value can only be reached through the `borrow()` / `borrow_mut()` guards,
+/// so the start and end of every borrow show up in the timeline.
+pub struct Traced<T> {
+    // Always Some, except after into_inner() has taken it out
+    value: Option<T>,
+    id: u64,
+    label: Rc<str>,
+    tracer: Tracer,
+}
+
+impl<T> Traced<T> {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    fn record(&self, kind: EventKind) {
+        self.tracer.record(self.id, &self.label, kind);
+    }
+
+    fn value(&self) -> &T {
+        self.value.as_ref().expect("value is only taken by into_inner")
+    }
+
+    fn value_mut(&mut self) -> &mut T {
+        self.value.as_mut().expect("value is only taken by into_inner")
+    }
+
+    /// Takes an immutable borrow guard: records Borrowed, and Released when the guard drops
+    pub fn borrow(&self) -> TraceRef<'_, T> {
+        self.record(EventKind::Borrowed);
+        TraceRef { traced: self }
+    }
+
+    /// Takes a mutable borrow guard: records BorrowedMut, and Released when the guard drops
+    pub fn borrow_mut(&mut self) -> TraceMut<'_, T> {
+        self.record(EventKind::BorrowedMut);
+        TraceMut { traced: self }
+    }
+
+    /// Marks a Move explicitly (Rust moves themselves can't be intercepted)
+    pub fn moved_to(self, to: &str) -> Traced<T> {
+        self.record(EventKind::Moved { to: String::from(to) });
+        self
+    }
+
+    /// Stops tracking and hands out the inner value
+    pub fn into_inner(mut self) -> T {
+        self.record(EventKind::Unwrapped);
+        // A field can't be moved out of a type that implements Drop, but it can be
+        // take()n. Drop then sees None and records nothing.
+        self.value.take().expect("value is only taken by into_inner")
+    }
+}
+
+impl<T: Clone> Clone for Traced<T> {
+    fn clone(&self) -> Traced<T> {
+        // Deep-copy the inner value and assign a new id
+        self.tracer.register(
+            Rc::clone(&self.label),
+            self.value().clone(),
+            EventKind::Cloned { from: self.id },
+        )
+    }
+}
+
+impl<T> Drop for Traced<T> {
+    fn drop(&mut self) {
+        if self.value.is_some() {
+            self.record(EventKind::Dropped);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Traced<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Traced")
+            .field("id", &self.id)
+            .field("label", &self.label)
+            .field("value", self.value())
+            .finish()
+    }
+}
+
+/// Immutable borrow guard (like RefCell's Ref)
+pub struct TraceRef<'t, T> {
+    traced: &'t Traced<T>,
+}
+
+impl<T> Deref for TraceRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.traced.value()
+    }
+}
+
+impl<T> Drop for TraceRef<'_, T> {
+    fn drop(&mut self) {
+        self.traced.record(EventKind::Released);
+    }
+}
+
+/// Mutable borrow guard (like RefCell's RefMut)
+pub struct TraceMut<'t, T> {
+    traced: &'t mut Traced<T>,
+}
+
+impl<T> Deref for TraceMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.traced.value()
+    }
+}
+
+impl<T> DerefMut for TraceMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.traced.value_mut()
+    }
+}
+
+impl<T> Drop for TraceMut<'_, T> {
+    fn drop(&mut self) {
+        self.traced.record(EventKind::Released);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(tracer: &Tracer) -> Vec<(u64, EventKind)> {
+        tracer.events().into_iter().map(|e| (e.id, e.kind)).collect()
+    }
+
+    #[test]
+    fn test_clone_and_drop_timeline() {
+        let tracer = Tracer::new();
+        {
+            let a = tracer.track("a", String::from("hello"));
+            let b = a.clone();
+            assert_ne!(a.id(), b.id());
+            drop(a);
+            // b is dropped automatically at the end of the scope
+        }
+        assert_eq!(
+            kinds(&tracer),
+            vec![
+                (1, EventKind::Created),
+                (2, EventKind::Cloned { from: 1 }),
+                (1, EventKind::Dropped),
+                (2, EventKind::Dropped),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_borrow_guards() {
+        let tracer = Tracer::new();
+        let mut v = tracer.track("v", vec![1, 2, 3]);
+        {
+            let r1 = v.borrow();
+            let r2 = v.borrow(); // several immutable borrows can coexist
+            assert_eq!(r1.len() + r2.len(), 6);
+        }
+        v.borrow_mut().push(4); // the temporary guard is dropped at the end of the statement
+        assert_eq!(*v.borrow(), vec![1, 2, 3, 4]);
+
+        let ev: Vec<EventKind> = tracer.events_for(v.id()).into_iter().map(|e| e.kind).collect();
+        assert_eq!(
+            ev,
+            vec![
+                EventKind::Created,
+                EventKind::Borrowed,
+                EventKind::Borrowed,
+                EventKind::Released, // r2 is dropped first (reverse order)
+                EventKind::Released,
+                EventKind::BorrowedMut,
+                EventKind::Released,
+                EventKind::Borrowed,
+                EventKind::Released,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_moved_to_and_into_inner() {
+        let tracer = Tracer::new();
+        let s = tracer.track("s1", String::from("data"));
+        let s = s.moved_to("s2");
+        let inner = s.into_inner();
+        assert_eq!(inner, "data");
+
+        // No Dropped after into_inner
+        assert_eq!(
+            kinds(&tracer),
+            vec![
+                (1, EventKind::Created),
+                (1, EventKind::Moved { to: String::from("s2") }),
+                (1, EventKind::Unwrapped),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dump() {
+        let tracer = Tracer::new();
+        let a = tracer.track("a", 1);
+        let _b = a.clone();
+        drop(a);
+        tracer.clear();
+        let c = tracer.track("c", 2);
+        drop(c);
+
+        // After clear, ids keep counting up while sequence numbers restart
+        assert_eq!(tracer.dump(), "[  0] c#3 created\n[  1] c#3 dropped\n");
+    }
+}
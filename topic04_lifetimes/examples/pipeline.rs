@@ -0,0 +1,66 @@
+// 把 examples/complex_lifetimes.rs 的三层生命周期 ('a Context、'b Processor、'c 请求)
+// 换成库里的 pipeline 模块：多个处理器串成中间件链。
+//
+// cargo run -p topic04_lifetimes --example pipeline
+
+use topic04_lifetimes::pipeline::{Context, Echo, Logger, Next, Pipeline, Processor, Request, Response};
+
+fn main() {
+    let context_data = String::from("System Context");
+    let processor_data = String::from("Processor A");
+
+    // 'a: 上下文，存活时间最长
+    let context = Context::new(&context_data).with_setting("version", "1.0");
+
+    {
+        // 'b: 处理器，生命周期比 'a 短
+        let logger = Logger::new("logger");
+        let echo = Echo { id: String::from("echo") };
+        let processor = Worker { id: &processor_data };
+        let pipeline = Pipeline::new(&context)
+            .with(&logger)
+            .with(&echo)
+            .with(&processor);
+
+        let result;
+        {
+            // 'c: 临时请求，生命周期最短
+            let request = String::from("Request #1");
+
+            // Pipeline::execute 的签名里，Response 只和 'b 有关，和请求无关：
+            // 所以请求死掉以后，结果仍然可以使用。
+            result = pipeline.execute(&Request::new("/work", &request));
+
+            let echoed = pipeline.execute(&Request::new("/echo", &request));
+            println!("Echo: {} (handled by {:?})", echoed.body, echoed.handled_by);
+        }
+        println!("Result: {} (handled by {:?})", result.body, result.handled_by);
+        println!("Log: {:?}", logger.entries());
+    }
+}
+
+// 这个处理器展示了复杂的生命周期依赖关系：
+// 它借用了外部的 processor_data ('b)，处理请求时返回自己的 ID 作为凭证。
+struct Worker<'b> {
+    id: &'b str,
+}
+
+impl<'a, 'b> Processor<'a> for Worker<'b> {
+    fn name(&self) -> &str {
+        self.id
+    }
+
+    fn process<'p>(&'p self, ctx: &'p Context<'a>, req: &Request<'_>, next: Next<'_, 'p, 'a>) -> Response<'p> {
+        if req.path != "/work" {
+            return next.run(req);
+        }
+        println!("Context [{}] is using Processor [{}] to handle '{}'",
+                 ctx.name, self.id, req.body);
+
+        // 因为返回的是 self.id，它的生命周期至少是 'p。
+        // 由于 &'p Context<'a> 隐含了 'a: 'p，返回 context 里的东西也是安全的（降级为 'p）。
+        // 但是我们不能返回 req.body，因为请求的生命周期可能比 'p 短：
+        // Response::ok(req.body) 会编译失败。
+        Response::ok(self.id)
+    }
+}
@@ -1,3 +1,5 @@
+use topic04_lifetimes::split;
+
 fn main() {
     // 'a: text 拥有最长的生命周期，它是我们想要返回的数据源
     let text = String::from("The quick brown fox jumps over the lazy dog");
@@ -32,11 +34,7 @@ fn extract_prefix<'a, 'b, 'c>(
     annotation: &'c str
 ) -> &'a str {
     println!("Log [{}]: Splitting text by '{}'", annotation, delimiter);
-    
-    if let Some(index) = text.find(delimiter) {
-        // 返回 text 的一个切片，它的生命周期自然也是 'a
-        return &text[..index];
-    }
-    
-    text
+
+    // 真正的切分交给库：split::extract_prefix 的签名同样只把返回值和 text 绑定
+    split::extract_prefix(text, delimiter)
 }
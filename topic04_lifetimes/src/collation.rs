@@ -0,0 +1,288 @@
+// Collation: 比字节序更符合人类直觉的字符串排序
+//
+// longer_term 用 `>=` 比较，也就是按 UTF-8 字节比较：
+// - "apple" > "Banana"，因为 'a' (97) > 'B' (66)
+// - "é" > "z"，因为 'é' 编码成 0xC3 0xA9，比 'z' (0x7A) 大
+// - "file10" < "file2"，因为 '1' < '2'
+//
+// 这里提供三种排序规则，每种都有两种用法：
+// 1. 比较器函数：cmp_case_insensitive / cmp_accent_insensitive / cmp_natural，
+//    可以传给 select::select_with、slice::sort_by 或下面的 longer_term_by。
+// 2. Ord 包装：CaseInsensitive(&str) / AccentInsensitive(&str) / Natural(&str)，
+//    可以放进 BTreeMap、直接调用 max()、sort()。包装只借用字符串，不拷贝。
+//
+// 注意：比较器不做额外的“决胜”，"Apple" 和 "apple" 在 CaseInsensitive 下就是相等的。
+// 需要稳定结果时，配合稳定排序或 Ties 策略使用；
+// 需要全序时用 select::cmp::case_insensitive，它在相等时再按字节序区分。
+//
+// 重音折叠只是一张手写的小表，不是完整的 Unicode 排序规则 (UCA)：
+// - 覆盖 Latin-1 补充 (À-ÿ) 和 Latin Extended-A (Ā-ſ) 里带附加符号的字母，
+//   以及组合附加符号 U+0300-U+036F（"e\u{301}" 和 "é" 相等）。
+// - 范围之外：其他组合符号、Latin Extended-B 及之后的字母、Ð/Þ/Ŋ/ĸ 这类独立字母、
+//   希腊文和西里尔文的重音，都只做小写折叠。
+// - 不考虑语言习惯（德语 ä 排在 a 后，瑞典语排在 z 后，这里一律当作 a）。
+
+use std::cmp::Ordering;
+
+/// 把一个字符折叠成“基本字母”：去掉重音、转成小写，组合附加符号直接丢掉。
+/// 少数连字会展开成两个字符 (ß -> ss, æ -> ae, œ -> oe, ĳ -> ij)。
+/// 覆盖范围见文件开头。
+fn fold_char(c: char) -> impl Iterator<Item = char> {
+    let folded: [char; 2] = match c {
+        'À'..='Å' | 'à'..='å' | 'Ā'..='ą' => ['a', '\0'],
+        'Ç' | 'ç' | 'Ć'..='č' => ['c', '\0'],
+        'Ď'..='đ' => ['d', '\0'],
+        'È'..='Ë' | 'è'..='ë' | 'Ē'..='ě' => ['e', '\0'],
+        'Ĝ'..='ģ' => ['g', '\0'],
+        'Ĥ'..='ħ' => ['h', '\0'],
+        'Ì'..='Ï' | 'ì'..='ï' | 'Ĩ'..='ı' => ['i', '\0'],
+        'Ĵ' | 'ĵ' => ['j', '\0'],
+        'Ķ' | 'ķ' => ['k', '\0'],
+        'Ĺ'..='ł' => ['l', '\0'],
+        'Ñ' | 'ñ' | 'Ń'..='ň' => ['n', '\0'],
+        'Ò'..='Ö' | 'Ø' | 'ò'..='ö' | 'ø' | 'Ō'..='ő' => ['o', '\0'],
+        'Ŕ'..='ř' => ['r', '\0'],
+        'Ś'..='š' => ['s', '\0'],
+        'Ţ'..='ŧ' => ['t', '\0'],
+        'Ù'..='Ü' | 'ù'..='ü' | 'Ũ'..='ų' => ['u', '\0'],
+        'Ŵ' | 'ŵ' => ['w', '\0'],
+        'Ý' | 'ý' | 'ÿ' | 'Ŷ'..='Ÿ' => ['y', '\0'],
+        'Ź'..='ž' => ['z', '\0'],
+        'ſ' => ['s', '\0'],
+        'ß' => ['s', 's'],
+        'Ĳ' | 'ĳ' => ['i', 'j'],
+        'Æ' | 'æ' => ['a', 'e'],
+        'Œ' | 'œ' => ['o', 'e'],
+        // 组合附加符号 (Combining Diacritical Marks)：重音已经和字母分开了，丢掉即可
+        '\u{0300}'..='\u{036F}' => ['\0', '\0'],
+        _ => {
+            // 其他字符：只做小写折叠（大多数字符小写后仍是一个字符）
+            let mut lower = c.to_lowercase();
+            match (lower.next(), lower.next()) {
+                (Some(a), None) => [a, '\0'],
+                (Some(a), Some(b)) => [a, b],
+                _ => [c, '\0'],
+            }
+        }
+    };
+    folded.into_iter().filter(|&ch| ch != '\0')
+}
+
+/// 忽略大小写（Unicode 小写折叠）。不分配新字符串。
+pub fn cmp_case_insensitive(a: &str, b: &str) -> Ordering {
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .cmp(b.chars().flat_map(char::to_lowercase))
+}
+
+/// 忽略重音和大小写："é" == "E"，"école" < "zèbre"。不分配新字符串。
+pub fn cmp_accent_insensitive(a: &str, b: &str) -> Ordering {
+    a.chars().flat_map(fold_char).cmp(b.chars().flat_map(fold_char))
+}
+
+/// 把字符串切成“数字段”和“非数字段”
+fn natural_chunks(s: &str) -> impl Iterator<Item = &str> {
+    let mut rest = s;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let is_digit = first.is_ascii_digit();
+        let len = rest
+            .find(|c: char| c.is_ascii_digit() != is_digit)
+            .unwrap_or(rest.len());
+        let (chunk, tail) = rest.split_at(len);
+        rest = tail;
+        Some(chunk)
+    })
+}
+
+/// 比较两个纯数字串的数值：支持任意长度，不会溢出。
+/// 数值相等时，前导零少的排在前面 ("1" < "01")。
+fn cmp_digits(a: &str, b: &str) -> Ordering {
+    let (ta, tb) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+    ta.len()
+        .cmp(&tb.len())
+        .then_with(|| ta.cmp(tb))
+        .then_with(|| a.len().cmp(&b.len()))
+}
+
+/// 自然排序："file2" < "file10"，"v1.9" < "v1.10"。
+/// 数字段按数值比较，其他部分忽略重音和大小写。
+pub fn cmp_natural(a: &str, b: &str) -> Ordering {
+    let mut ca = natural_chunks(a);
+    let mut cb = natural_chunks(b);
+    loop {
+        match (ca.next(), cb.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let x_digit = x.starts_with(|c: char| c.is_ascii_digit());
+                let y_digit = y.starts_with(|c: char| c.is_ascii_digit());
+                let ord = match (x_digit, y_digit) {
+                    (true, true) => cmp_digits(x, y),
+                    // 数字排在文字前面
+                    (true, false) => Ordering::Less,
+                    (false, true) => Ordering::Greater,
+                    (false, false) => cmp_accent_insensitive(x, y),
+                };
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+        }
+    }
+}
+
+/// longer_term 的通用版：按 `cmp` 返回“更大”的那个，相等时返回 s1。
+pub fn longer_term_by<'a, F>(s1: &'a str, s2: &'a str, cmp: F) -> &'a str
+where
+    F: FnOnce(&str, &str) -> Ordering,
+{
+    if cmp(s1, s2) == Ordering::Less {
+        s2
+    } else {
+        s1
+    }
+}
+
+// 为一个 &str 包装类型实现 PartialEq / Eq / PartialOrd / Ord，全部委托给同一个比较器，
+// 保证 Eq 和 Ord 一致。
+macro_rules! collation_wrapper {
+    ($(#[$doc:meta])* $name:ident, $cmp:path) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name<'a>(pub &'a str);
+
+        impl<'a> $name<'a> {
+            pub fn as_str(&self) -> &'a str {
+                self.0
+            }
+        }
+
+        impl PartialEq for $name<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                $cmp(self.0, other.0) == Ordering::Equal
+            }
+        }
+
+        impl Eq for $name<'_> {}
+
+        impl PartialOrd for $name<'_> {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name<'_> {
+            fn cmp(&self, other: &Self) -> Ordering {
+                $cmp(self.0, other.0)
+            }
+        }
+    };
+}
+
+collation_wrapper!(
+    /// 按 cmp_case_insensitive 排序的 &str
+    CaseInsensitive,
+    cmp_case_insensitive
+);
+collation_wrapper!(
+    /// 按 cmp_accent_insensitive 排序的 &str
+    AccentInsensitive,
+    cmp_accent_insensitive
+);
+collation_wrapper!(
+    /// 按 cmp_natural 排序的 &str
+    Natural,
+    cmp_natural
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::longer_term;
+    use crate::select::cmp::case_insensitive;
+    use crate::select::{select_with, Ties};
+
+    #[test]
+    fn test_byte_order_surprises() {
+        assert_eq!(longer_term("apple", "Banana"), "apple");
+        assert_eq!(longer_term("é", "z"), "é");
+        assert_eq!(longer_term("file10", "file2"), "file2");
+
+        assert_eq!(longer_term_by("apple", "Banana", cmp_case_insensitive), "Banana");
+        assert_eq!(longer_term_by("é", "z", cmp_accent_insensitive), "z");
+        assert_eq!(longer_term_by("file10", "file2", cmp_natural), "file10");
+        // 相等时返回 s1
+        assert_eq!(longer_term_by("Equal", "EQUAL", cmp_case_insensitive), "Equal");
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        assert_eq!(cmp_case_insensitive("HELLO", "hello"), Ordering::Equal);
+        assert_eq!(cmp_case_insensitive("abc", "ABD"), Ordering::Less);
+        assert_eq!(CaseInsensitive("Rust"), CaseInsensitive("rUST"));
+        assert!(CaseInsensitive("Zebra") > CaseInsensitive("apple"));
+    }
+
+    #[test]
+    fn test_accent_insensitive() {
+        assert_eq!(cmp_accent_insensitive("école", "ECOLE"), Ordering::Equal);
+        assert_eq!(cmp_accent_insensitive("Straße", "strasse"), Ordering::Equal);
+        assert_eq!(cmp_accent_insensitive("Ærø", "aero"), Ordering::Equal);
+        // Latin Extended-A 的整块字母
+        assert_eq!(cmp_accent_insensitive("Ĥħŧľĳ", "hhtlij"), Ordering::Equal);
+        assert_eq!(cmp_accent_insensitive("Łódź", "lodz"), Ordering::Equal);
+        // 组合形式 (e + U+0301) 和预组合形式 (é) 相等
+        assert_eq!(cmp_accent_insensitive("e\u{301}cole", "école"), Ordering::Equal);
+        // 范围之外：只做小写折叠
+        assert_eq!(cmp_accent_insensitive("Þór", "thor"), Ordering::Greater);
+
+        let mut words = vec!["zèbre", "Éclair", "apple", "eagle", "Ångström"];
+        words.sort_by(|a, b| cmp_accent_insensitive(a, b));
+        assert_eq!(words, vec!["Ångström", "apple", "eagle", "Éclair", "zèbre"]);
+    }
+
+    #[test]
+    fn test_natural() {
+        let mut files = vec!["file10.txt", "file2.txt", "File1.txt", "file02.txt", "file1b.txt"];
+        files.sort_by(|a, b| cmp_natural(a, b));
+        assert_eq!(files, vec!["File1.txt", "file1b.txt", "file2.txt", "file02.txt", "file10.txt"]);
+
+        assert!(Natural("v1.10") > Natural("v1.9"));
+        assert!(Natural("2") < Natural("a"));
+        // 超长数字也不会溢出
+        assert!(Natural("x99999999999999999999999") < Natural("x100000000000000000000000"));
+    }
+
+    #[test]
+    fn test_wrappers_in_collections() {
+        let words = ["banana", "Apple", "cherry"];
+        let max = words.iter().map(|w| CaseInsensitive(w)).max().unwrap();
+        assert_eq!(max.as_str(), "cherry");
+
+        let mut set = std::collections::BTreeSet::new();
+        for w in ["Hello", "HELLO", "world"] {
+            set.insert(CaseInsensitive(w));
+        }
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_with_select() {
+        let items = ["résumé", "Resume", "resume"];
+        // 全部相等：Ties::First 取第一个
+        assert_eq!(select_with(&items, cmp_accent_insensitive, Ties::First), Some("résumé"));
+        assert_eq!(select_with(&items, cmp_accent_insensitive, Ties::Last), Some("resume"));
+
+        // cmp_case_insensitive 没有决胜规则：忽略大小写后相等就由 Ties 决定。
+        // select::cmp::case_insensitive 在它的基础上再按字节序区分，是全序，与 Ties 无关。
+        assert_eq!(select_with(&["ÉCOLE", "école"], cmp_case_insensitive, Ties::First), Some("ÉCOLE"));
+        assert_eq!(select_with(&["ÉCOLE", "école"], cmp_case_insensitive, Ties::Last), Some("école"));
+        for ties in [Ties::First, Ties::Last] {
+            assert_eq!(select_with(&["ÉCOLE", "école"], case_insensitive, ties), Some("école"));
+        }
+        assert_eq!(select_with(&["école", "zèbre", "ecole"], cmp_accent_insensitive, Ties::First), Some("zèbre"));
+        assert_eq!(select_with(&["file9", "file10"], cmp_natural, Ties::First), Some("file10"));
+    }
+}
@@ -0,0 +1,404 @@
+// Config: 零拷贝的 INI 配置解析
+//
+// 和 Document、split 一样的模式：输入是 &'a str，解析结果里的所有名字都是 &'a str，
+// 直接指向原文，不拷贝。
+//
+// let text = fs::read_to_string("app.ini")?;
+// let config = Config::parse(&text)?;      // Config<'a> 借用 text
+// let port = config.get("server", "port");
+//
+// 支持的语法：
+//
+// ; 注释，也可以用 #
+// name = global            ; 出现在任何 [section] 之前的键属于全局段 ("")
+//
+// [server]
+// host = localhost         # 行尾注释：; 或 # 前面必须有空白
+// motd = "hello ; world"   ; 双引号：支持 \" \\ \n \t 转义，注释符号不生效
+// path = 'C:\no\escape'    ; 单引号：原样保留
+// list = a, b, \
+//        c                 ; 行尾的 \ 表示续行，和下一行用一个空格拼接
+//
+// 值的类型是 Cow<'a, str>：
+// - 普通值、没有转义的引号值：Cow::Borrowed，直接是原文切片。
+// - 有转义或续行的值：原文里不存在拼好的字符串，只能 Cow::Owned。
+
+use std::borrow::Cow;
+use std::fmt;
+
+/// 一个键值对
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry<'a> {
+    pub key: &'a str,
+    pub value: Cow<'a, str>,
+    /// 键所在的行号（从 1 开始）
+    pub line: usize,
+}
+
+/// 一个段。同名的段会合并成一个。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub name: &'a str,
+    /// 第一次出现的行号；全局段为 0
+    pub line: usize,
+    entries: Vec<Entry<'a>>,
+}
+
+impl<'a> Section<'a> {
+    /// 按出现顺序的所有键值对（包括重复的键）
+    pub fn entries(&self) -> &[Entry<'a>] {
+        &self.entries
+    }
+
+    /// 键对应的值；重复的键以最后一个为准
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entry(key).map(|e| e.value.as_ref())
+    }
+
+    pub fn entry(&self, key: &str) -> Option<&Entry<'a>> {
+        self.entries.iter().rev().find(|e| e.key == key)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// `[section` 缺少 `]`
+    UnterminatedSection,
+    /// `[]` 或 `[  ]`
+    EmptySectionName,
+    /// 既不是段、也不是注释，却没有 `=`
+    MissingEquals,
+    /// `= value`
+    EmptyKey,
+    /// 引号没有闭合
+    UnterminatedQuote,
+    /// 双引号里不认识的转义，如 `\q`
+    InvalidEscape(char),
+    /// 引号或 `]` 之后还有除注释以外的内容
+    TrailingCharacters,
+    /// 最后一行以 `\` 结尾
+    UnexpectedEof,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 出错的行号（从 1 开始）
+    pub line: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ParseErrorKind::UnterminatedSection => write!(f, "missing ']' in section header"),
+            ParseErrorKind::EmptySectionName => write!(f, "empty section name"),
+            ParseErrorKind::MissingEquals => write!(f, "expected 'key = value'"),
+            ParseErrorKind::EmptyKey => write!(f, "empty key"),
+            ParseErrorKind::UnterminatedQuote => write!(f, "unterminated quoted value"),
+            ParseErrorKind::InvalidEscape(c) => write!(f, "invalid escape '\\{}'", c),
+            ParseErrorKind::TrailingCharacters => write!(f, "unexpected characters after value"),
+            ParseErrorKind::UnexpectedEof => write!(f, "line continuation at end of input"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config<'a> {
+    // sections[0] 永远是全局段 ""
+    sections: Vec<Section<'a>>,
+}
+
+impl<'a> Config<'a> {
+    pub fn parse(text: &'a str) -> Result<Config<'a>, ParseError> {
+        let mut sections = vec![Section {
+            name: "",
+            line: 0,
+            entries: Vec::new(),
+        }];
+        let mut current = 0;
+        let mut lines = text.lines().enumerate().map(|(i, l)| (i + 1, l));
+
+        while let Some((line_no, raw)) = lines.next() {
+            let err = |kind| ParseError { line: line_no, kind };
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with([';', '#']) {
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix('[') {
+                let (name, tail) = rest
+                    .split_once(']')
+                    .ok_or(err(ParseErrorKind::UnterminatedSection))?;
+                if !is_blank_or_comment(tail) {
+                    return Err(err(ParseErrorKind::TrailingCharacters));
+                }
+                let name = name.trim();
+                if name.is_empty() {
+                    return Err(err(ParseErrorKind::EmptySectionName));
+                }
+                current = match sections.iter().position(|s| s.name == name) {
+                    Some(i) => i,
+                    None => {
+                        sections.push(Section {
+                            name,
+                            line: line_no,
+                            entries: Vec::new(),
+                        });
+                        sections.len() - 1
+                    }
+                };
+                continue;
+            }
+
+            let (key, rest) = line.split_once('=').ok_or(err(ParseErrorKind::MissingEquals))?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(err(ParseErrorKind::EmptyKey));
+            }
+            let value = parse_value(rest.trim(), line_no, &mut lines)?;
+            sections[current].entries.push(Entry {
+                key,
+                value,
+                line: line_no,
+            });
+        }
+        Ok(Config { sections })
+    }
+
+    /// 所有段，按第一次出现的顺序；第一个是全局段 ""
+    pub fn sections(&self) -> &[Section<'a>] {
+        &self.sections
+    }
+
+    /// 段名为 "" 时返回全局段
+    pub fn section(&self, name: &str) -> Option<&Section<'a>> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.section(section)?.get(key)
+    }
+
+    pub fn entry(&self, section: &str, key: &str) -> Option<&Entry<'a>> {
+        self.section(section)?.entry(key)
+    }
+}
+
+fn is_blank_or_comment(s: &str) -> bool {
+    let s = s.trim_start();
+    s.is_empty() || s.starts_with([';', '#'])
+}
+
+/// 去掉未加引号的值后面的行尾注释：`;` 或 `#` 前面必须是空白
+// s 总是紧跟在 `=` 之后（或是续行的行首），并且已经去掉了开头的空白，
+// 所以开头就相当于“前面有空白”：`key = ; note` 的值是空的。
+fn strip_inline_comment(s: &str) -> &str {
+    let mut prev_ws = true;
+    for (i, c) in s.char_indices() {
+        if prev_ws && (c == ';' || c == '#') {
+            return s[..i].trim_end();
+        }
+        prev_ws = c.is_whitespace();
+    }
+    s.trim_end()
+}
+
+// value: 已经去掉首尾空白的 `=` 右边部分
+fn parse_value<'a>(
+    value: &'a str,
+    line_no: usize,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Cow<'a, str>, ParseError> {
+    let err = |line, kind| ParseError { line, kind };
+
+    if let Some(rest) = value.strip_prefix('\'') {
+        let (inner, tail) = rest
+            .split_once('\'')
+            .ok_or(err(line_no, ParseErrorKind::UnterminatedQuote))?;
+        if !is_blank_or_comment(tail) {
+            return Err(err(line_no, ParseErrorKind::TrailingCharacters));
+        }
+        return Ok(Cow::Borrowed(inner));
+    }
+
+    if let Some(rest) = value.strip_prefix('"') {
+        // 先假设没有转义，直到遇到第一个 '\' 才开始分配
+        let mut owned: Option<String> = None;
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    if !is_blank_or_comment(&rest[i + 1..]) {
+                        return Err(err(line_no, ParseErrorKind::TrailingCharacters));
+                    }
+                    return Ok(match owned {
+                        Some(s) => Cow::Owned(s),
+                        None => Cow::Borrowed(&rest[..i]),
+                    });
+                }
+                '\\' => {
+                    let escaped = match chars.next() {
+                        Some((_, '"')) => '"',
+                        Some((_, '\\')) => '\\',
+                        Some((_, 'n')) => '\n',
+                        Some((_, 't')) => '\t',
+                        Some((_, other)) => return Err(err(line_no, ParseErrorKind::InvalidEscape(other))),
+                        None => break,
+                    };
+                    owned.get_or_insert_with(|| String::from(&rest[..i])).push(escaped);
+                }
+                _ => {
+                    if let Some(s) = owned.as_mut() {
+                        s.push(c);
+                    }
+                }
+            }
+        }
+        return Err(err(line_no, ParseErrorKind::UnterminatedQuote));
+    }
+
+    let value = strip_inline_comment(value);
+    let Some(head) = value.strip_suffix('\\') else {
+        return Ok(Cow::Borrowed(value));
+    };
+
+    // 续行：把后面的行依次拼上
+    let mut joined = String::from(head.trim_end());
+    let mut last_line = line_no;
+    loop {
+        let (next_no, next) = lines
+            .next()
+            .ok_or(err(last_line, ParseErrorKind::UnexpectedEof))?;
+        last_line = next_no;
+        let part = strip_inline_comment(next.trim());
+        let (part, more) = match part.strip_suffix('\\') {
+            Some(p) => (p.trim_end(), true),
+            None => (part, false),
+        };
+        if !part.is_empty() {
+            if !joined.is_empty() {
+                joined.push(' ');
+            }
+            joined.push_str(part);
+        }
+        if !more {
+            return Ok(Cow::Owned(joined));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+; global settings
+name = demo
+
+[server]
+host = localhost   # the host
+port=8080
+motd = \"hello ; world\"
+escaped = \"say \\\"hi\\\"\\n\"
+path = 'C:\\no\\escape'
+list = a, b, \\
+       c, \\
+       d
+
+[database]
+url = postgres://localhost/db#frag
+[server]
+port = 9090
+";
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(SAMPLE).unwrap();
+        let names: Vec<&str> = config.sections().iter().map(|s| s.name).collect();
+        assert_eq!(names, vec!["", "server", "database"]);
+
+        assert_eq!(config.get("", "name"), Some("demo"));
+        assert_eq!(config.get("server", "host"), Some("localhost"));
+        assert_eq!(config.get("server", "motd"), Some("hello ; world"));
+        assert_eq!(config.get("server", "escaped"), Some("say \"hi\"\n"));
+        assert_eq!(config.get("server", "path"), Some("C:\\no\\escape"));
+        assert_eq!(config.get("server", "list"), Some("a, b, c, d"));
+        // '#' 前面没有空白，不是注释
+        assert_eq!(config.get("database", "url"), Some("postgres://localhost/db#frag"));
+        // 同名段合并，重复的键以最后一个为准
+        assert_eq!(config.get("server", "port"), Some("9090"));
+        assert_eq!(config.section("server").unwrap().entries().len(), 7);
+        assert_eq!(config.get("server", "missing"), None);
+        assert_eq!(config.get("missing", "host"), None);
+    }
+
+    #[test]
+    fn test_zero_copy() {
+        let text = String::from(SAMPLE);
+        let config = Config::parse(&text).unwrap();
+        let range = text.as_bytes().as_ptr_range();
+
+        let host = config.entry("server", "host").unwrap();
+        assert!(matches!(host.value, Cow::Borrowed(_)));
+        assert!(range.contains(&host.value.as_ptr()));
+        assert!(range.contains(&host.key.as_ptr()));
+        assert!(range.contains(&config.sections()[1].name.as_ptr()));
+        assert!(matches!(config.entry("server", "motd").unwrap().value, Cow::Borrowed(_)));
+        assert!(matches!(config.entry("server", "path").unwrap().value, Cow::Borrowed(_)));
+
+        // 有转义或续行的值才需要分配
+        assert!(matches!(config.entry("server", "escaped").unwrap().value, Cow::Owned(_)));
+        assert!(matches!(config.entry("server", "list").unwrap().value, Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_line_numbers() {
+        let config = Config::parse(SAMPLE).unwrap();
+        assert_eq!(config.entry("", "name").unwrap().line, 2);
+        assert_eq!(config.entry("server", "list").unwrap().line, 10);
+        assert_eq!(config.entry("server", "port").unwrap().line, 17);
+        assert_eq!(config.section("database").unwrap().line, 14);
+    }
+
+    #[test]
+    fn test_errors() {
+        let cases = [
+            ("[server\nhost = x", 1, ParseErrorKind::UnterminatedSection),
+            ("a = 1\n[ ]", 2, ParseErrorKind::EmptySectionName),
+            ("a = 1\n\njust text", 3, ParseErrorKind::MissingEquals),
+            ("= 1", 1, ParseErrorKind::EmptyKey),
+            ("a = \"open", 1, ParseErrorKind::UnterminatedQuote),
+            ("a = 'open", 1, ParseErrorKind::UnterminatedQuote),
+            ("a = \"bad \\q\"", 1, ParseErrorKind::InvalidEscape('q')),
+            ("a = \"x\" y", 1, ParseErrorKind::TrailingCharacters),
+            ("[s] x", 1, ParseErrorKind::TrailingCharacters),
+            ("a = 1 \\\n  2 \\", 2, ParseErrorKind::UnexpectedEof),
+        ];
+        for (text, line, kind) in cases {
+            assert_eq!(Config::parse(text), Err(ParseError { line, kind }), "{:?}", text);
+        }
+
+        let e = Config::parse("x\n[y").unwrap_err();
+        assert_eq!(e.to_string(), "line 1: expected 'key = value'");
+    }
+
+    #[test]
+    fn test_comments_and_crlf() {
+        let config = Config::parse("# c1\r\n; c2\r\n[a] ; c3\r\nk = v ; c4\r\nq = \"x\" # c5\r\n").unwrap();
+        assert_eq!(config.get("a", "k"), Some("v"));
+        assert_eq!(config.get("a", "q"), Some("x"));
+        assert_eq!(config.get("a", "empty"), None);
+        assert_eq!(Config::parse("k =").unwrap().get("", "k"), Some(""));
+
+        // `=` 后面直接是注释：值为空
+        let config = Config::parse("a = ; note\nb =   # note\nc =;x\nd = x#y\n").unwrap();
+        assert_eq!(config.get("", "a"), Some(""));
+        assert_eq!(config.get("", "b"), Some(""));
+        assert_eq!(config.get("", "c"), Some(""));
+        // 值中间的 # 前面没有空白，不是注释
+        assert_eq!(config.get("", "d"), Some("x#y"));
+    }
+}
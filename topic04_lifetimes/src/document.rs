@@ -0,0 +1,272 @@
+// Document: 借用一段文本，切出各种 ImportantExcerpt
+//
+// main.rs 的 example_struct 手动 split('.') 找到一句话，再包进 ImportantExcerpt。
+// Document<'a> 把这件事做成库：
+//
+// let novel = String::from("Call me Ishmael. Some years ago...");
+// let doc = Document::new(&novel);           // 'a = novel 的借用
+// let first = doc.sentences()[0];            // ImportantExcerpt<'a>
+// let hits = doc.keyword_in_context("years", 10);
+// let best = doc.rank("ishmael years");      // 按相关度排序
+//
+// 所有结果都是 &'a str 切片，指向 novel 本身，没有任何文本拷贝。
+// 只要 novel 还活着，这些 excerpt 就有效；novel 死了，编译器就不让用了。
+
+use std::ops::Range;
+
+use crate::segment::SentenceSplitter;
+
+/// 一段借用自原文的摘录
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImportantExcerpt<'a> {
+    pub part: &'a str,
+    /// part 在原文中的起始字节位置
+    pub start: usize,
+}
+
+impl<'a> ImportantExcerpt<'a> {
+    /// part 在原文中的字节区间
+    pub fn span(&self) -> Range<usize> {
+        self.start..self.start + self.part.len()
+    }
+}
+
+/// 带相关度分数的摘录
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ranked<'a> {
+    pub excerpt: ImportantExcerpt<'a>,
+    pub score: f64,
+}
+
+pub struct Document<'a> {
+    text: &'a str,
+}
+
+impl<'a> Document<'a> {
+    pub fn new(text: &'a str) -> Document<'a> {
+        Document { text }
+    }
+
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// 把原文中的 [start, end) 去掉首尾空白，包装成 excerpt；全是空白时返回 None
+    fn excerpt(&self, start: usize, end: usize) -> Option<ImportantExcerpt<'a>> {
+        let raw = &self.text[start..end];
+        let part = raw.trim();
+        if part.is_empty() {
+            return None;
+        }
+        let leading = raw.len() - raw.trim_start().len();
+        Some(ImportantExcerpt {
+            part,
+            start: start + leading,
+        })
+    }
+
+    /// 用默认的分句器切分句子（处理缩写、小数、省略号、引号和中文标点）
+    pub fn sentences(&self) -> Vec<ImportantExcerpt<'a>> {
+        self.sentences_with(&SentenceSplitter::default())
+    }
+
+    /// 用自定义的分句器切分句子
+    pub fn sentences_with(&self, splitter: &SentenceSplitter) -> Vec<ImportantExcerpt<'a>> {
+        splitter
+            .split(self.text)
+            .into_iter()
+            .map(|s| ImportantExcerpt {
+                part: s.text,
+                start: s.start,
+            })
+            .collect()
+    }
+
+    /// 按空行切分段落
+    pub fn paragraphs(&self) -> Vec<ImportantExcerpt<'a>> {
+        let mut out = Vec::new();
+        let mut start = 0;
+        let mut pos = 0;
+        for line in self.text.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                out.extend(self.excerpt(start, pos));
+                start = pos + line.len();
+            }
+            pos += line.len();
+        }
+        out.extend(self.excerpt(start, self.text.len()));
+        out
+    }
+
+    /// 关键词上下文 (KWIC)：每次命中（忽略 ASCII 大小写）返回关键词前后各 `radius` 字节的窗口。
+    /// 窗口边界会收缩到最近的字符边界上。
+    ///
+    /// 注意生命周期：keyword 的 'k 和返回值无关，用完就可以丢掉。
+    pub fn keyword_in_context<'k>(&self, keyword: &'k str, radius: usize) -> Vec<ImportantExcerpt<'a>> {
+        let mut out = Vec::new();
+        for at in find_ignore_ascii_case(self.text, keyword) {
+            let mut start = at.saturating_sub(radius);
+            while !self.text.is_char_boundary(start) {
+                start += 1;
+            }
+            let mut end = (at + keyword.len() + radius).min(self.text.len());
+            while !self.text.is_char_boundary(end) {
+                end -= 1;
+            }
+            out.extend(self.excerpt(start, end));
+        }
+        out
+    }
+
+    /// 按查询词的相关度给句子排序 (TF-IDF)，只返回至少命中一个词的句子。
+    /// 分数相同时保持原文顺序。
+    pub fn rank<'q>(&self, query: &'q str) -> Vec<Ranked<'a>> {
+        let sentences = self.sentences();
+        let terms: Vec<&'q str> = words(query).collect();
+        let n = sentences.len() as f64;
+
+        // df: 每个查询词出现在多少个句子里
+        let df: Vec<usize> = terms
+            .iter()
+            .map(|t| {
+                sentences
+                    .iter()
+                    .filter(|s| words(s.part).any(|w| w.eq_ignore_ascii_case(t)))
+                    .count()
+            })
+            .collect();
+
+        let mut ranked: Vec<Ranked<'a>> = sentences
+            .into_iter()
+            .map(|excerpt| {
+                let score = terms
+                    .iter()
+                    .zip(&df)
+                    .filter(|(_, &df)| df > 0)
+                    .map(|(t, &df)| {
+                        let tf = words(excerpt.part).filter(|w| w.eq_ignore_ascii_case(t)).count();
+                        tf as f64 * ((n / df as f64).ln() + 1.0)
+                    })
+                    .sum();
+                Ranked { excerpt, score }
+            })
+            .filter(|r| r.score > 0.0)
+            .collect();
+
+        // sort_by 是稳定排序，分数相同的句子保持原文顺序
+        ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+        ranked
+    }
+}
+
+/// 按非字母数字字符切词
+fn words(s: &str) -> impl Iterator<Item = &str> {
+    s.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty())
+}
+
+/// 所有（不重叠的）忽略 ASCII 大小写的匹配位置
+fn find_ignore_ascii_case(text: &str, pattern: &str) -> Vec<usize> {
+    let (hay, pat) = (text.as_bytes(), pattern.as_bytes());
+    let mut out = Vec::new();
+    if pat.is_empty() {
+        return out;
+    }
+    let mut i = 0;
+    while i + pat.len() <= hay.len() {
+        if hay[i..i + pat.len()].eq_ignore_ascii_case(pat) && text.is_char_boundary(i) {
+            out.push(i);
+            i += pat.len();
+        } else {
+            i += 1;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOVEL: &str = "Call me Ishmael. Some years ago, never mind how long precisely, \
+having little money, I thought I would sail about.\n\
+\n\
+It is a way I have of driving off the spleen! Whenever I find myself growing grim, \
+I account it high time to get to sea. Is it the sea?";
+
+    #[test]
+    fn test_sentences() {
+        let doc = Document::new(NOVEL);
+        let s = doc.sentences();
+        assert_eq!(s.len(), 5);
+        assert_eq!(s[0].part, "Call me Ishmael.");
+        assert!(s[1].part.starts_with("Some years ago"));
+        assert_eq!(s[4].part, "Is it the sea?");
+
+        // span 指回原文
+        for e in &s {
+            assert_eq!(&NOVEL[e.span()], e.part);
+        }
+    }
+
+    #[test]
+    fn test_sentences_with_abbreviations() {
+        let doc = Document::new("Dr. Smith paid 3.14 dollars. Some years ago...");
+        let parts: Vec<&str> = doc.sentences().iter().map(|s| s.part).collect();
+        assert_eq!(parts, vec!["Dr. Smith paid 3.14 dollars.", "Some years ago..."]);
+
+        let bare = SentenceSplitter::with_abbreviations([]);
+        assert_eq!(doc.sentences_with(&bare).len(), 3);
+    }
+
+    #[test]
+    fn test_no_copy() {
+        let novel = String::from("One. Two.");
+        let doc = Document::new(&novel);
+        let second = doc.sentences()[1];
+        // 指针落在 novel 的堆内存里：只是借用，没有拷贝
+        assert_eq!(second.part.as_ptr(), novel[5..].as_ptr());
+    }
+
+    #[test]
+    fn test_paragraphs() {
+        let doc = Document::new(NOVEL);
+        let p = doc.paragraphs();
+        assert_eq!(p.len(), 2);
+        assert!(p[0].part.ends_with("sail about."));
+        assert!(p[1].part.starts_with("It is a way"));
+        assert_eq!(Document::new("\n\n  \n").paragraphs(), vec![]);
+    }
+
+    #[test]
+    fn test_keyword_in_context() {
+        let doc = Document::new(NOVEL);
+        let hits = doc.keyword_in_context("SEA", 8);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].part, "get to sea. Is it");
+        assert_eq!(hits[1].part, "it the sea?");
+
+        // 窗口不会切在多字节字符中间：半个汉字会被收缩掉
+        let doc = Document::new("你好rust世界");
+        assert_eq!(doc.keyword_in_context("rust", 2)[0].part, "rust");
+        assert_eq!(doc.keyword_in_context("rust", 3)[0].part, "好rust世");
+    }
+
+    #[test]
+    fn test_rank() {
+        let doc = Document::new(NOVEL);
+        let query = String::from("sea ishmael");
+        let ranked = doc.rank(&query);
+        drop(query); // 结果只借用原文，不借用 query
+
+        let parts: Vec<&str> = ranked.iter().map(|r| r.excerpt.part).collect();
+        assert_eq!(parts.len(), 3);
+        // "Ishmael" 只出现一次，idf 最高
+        assert_eq!(parts[0], "Call me Ishmael.");
+        // 两句都只命中 "sea" 一次，分数相同，保持原文顺序
+        assert!(parts[1].ends_with("get to sea."));
+        assert_eq!(parts[2], "Is it the sea?");
+        assert_eq!(ranked[1].score, ranked[2].score);
+
+        assert!(doc.rank("whale").is_empty());
+    }
+}
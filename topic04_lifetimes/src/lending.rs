@@ -0,0 +1,220 @@
+// Lending Iterator: 返回值借用迭代器自己
+//
+// 标准库的 Iterator 写不出“复用同一个缓冲区”的按行读取：
+//
+// impl Iterator for LineReader {
+//     type Item = &'? str;               // 这里没有任何生命周期可以写
+//     fn next(&mut self) -> Option<&'? str>;
+// }
+//
+// Iterator::Item 是一个固定的类型，它不能和每次调用 next(&mut self) 的那个借用挂钩。
+// 所以 BufRead::lines() 只能每行分配一个新的 String。
+//
+// GAT (Generic Associated Types) 让关联类型也可以带生命周期参数：
+//
+// type Item<'a> where Self: 'a;
+// fn next(&mut self) -> Option<Self::Item<'_>>;
+//
+// next 返回的 Item 借用了 &mut self，所以：
+// - 上一行用完之前不能读下一行（编译器会报 E0499）；
+// - 代价换来的是：整个读取过程只有一个缓冲区，不再每行分配。
+
+use std::io::{self, BufRead};
+
+pub trait LendingIterator {
+    type Item<'a>
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+
+    /// 依次处理每一项。Item 只在闭包调用期间有效。
+    fn for_each<F>(mut self, mut f: F)
+    where
+        Self: Sized,
+        F: FnMut(Self::Item<'_>),
+    {
+        while let Some(item) = self.next() {
+            f(item);
+        }
+    }
+
+    /// 把每一项折叠进累加值。累加值不能借用 Item。
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item<'_>) -> B,
+    {
+        let mut acc = init;
+        while let Some(item) = self.next() {
+            acc = f(acc, item);
+        }
+        acc
+    }
+
+    fn count(self) -> usize
+    where
+        Self: Sized,
+    {
+        self.fold(0, |n, _| n + 1)
+    }
+}
+
+/// 按行读取，每行都是同一个内部缓冲区的切片（不含行尾的 \n 或 \r\n）
+pub struct LineReader<R> {
+    reader: R,
+    buf: String,
+    line_number: usize,
+}
+
+impl<R: BufRead> LineReader<R> {
+    pub fn new(reader: R) -> LineReader<R> {
+        LineReader::with_capacity(0, reader)
+    }
+
+    /// 预留缓冲区容量：不超过 capacity 字节的行不会触发任何分配
+    pub fn with_capacity(capacity: usize, reader: R) -> LineReader<R> {
+        LineReader {
+            reader,
+            buf: String::with_capacity(capacity),
+            line_number: 0,
+        }
+    }
+
+    /// 最近一次 next() 返回的行号（从 1 开始）；还没读过时为 0
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// 缓冲区容量：只会随最长的一行增长
+    pub fn buffer_capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead> LendingIterator for LineReader<R> {
+    type Item<'a>
+        = io::Result<&'a str>
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<io::Result<&str>> {
+        self.buf.clear();
+        match self.reader.read_line(&mut self.buf) {
+            Ok(0) => None,
+            Ok(_) => {
+                self.line_number += 1;
+                let line = self.buf.strip_suffix('\n').unwrap_or(&self.buf);
+                Some(Ok(line.strip_suffix('\r').unwrap_or(line)))
+            }
+            Err(e) => {
+                // 出错的行（比如不是合法的 UTF-8）也算一行
+                self.line_number += 1;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        let input = "first\nsecond\r\n\nlast";
+        let mut reader = LineReader::new(input.as_bytes());
+        let mut lines = Vec::new();
+        while let Some(line) = reader.next() {
+            // line 借用了 reader，必须在下一次 next() 之前拷贝出来
+            lines.push(line.unwrap().to_string());
+        }
+        assert_eq!(lines, vec!["first", "second", "", "last"]);
+        assert_eq!(reader.line_number(), 4);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_adapters() {
+        let input = "a\nbb\nccc\n";
+        assert_eq!(LineReader::new(input.as_bytes()).count(), 3);
+
+        let total = LineReader::new(input.as_bytes()).fold(0, |n, line| n + line.unwrap().len());
+        assert_eq!(total, 6);
+
+        let mut longest = String::new();
+        LineReader::new(input.as_bytes()).for_each(|line| {
+            let line = line.unwrap();
+            if line.len() > longest.len() {
+                longest = line.to_string();
+            }
+        });
+        assert_eq!(longest, "ccc");
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let input: &[u8] = b"ok\n\xff\xfe\nok again\n";
+        let mut reader = LineReader::new(input);
+        assert_eq!(reader.next().unwrap().unwrap(), "ok");
+        let err = reader.next().unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(reader.line_number(), 2);
+        assert_eq!(reader.next().unwrap().unwrap(), "ok again");
+    }
+
+    #[test]
+    fn test_no_allocation_per_line() {
+        let input = "some log line with a few words\n".repeat(1000);
+
+        // BufRead::lines()：每行一个新的 String（具体次数取决于 std 的实现，至少每行一次）
+        let (_, stats) = alloc_counter::measure(|| {
+            for line in input.as_bytes().lines() {
+                assert_eq!(line.unwrap().len(), 30);
+            }
+        });
+        assert!(stats.allocations >= 1000);
+
+        // LineReader：缓冲区预留够了，读 1000 行一次分配都没有
+        let mut reader = LineReader::with_capacity(64, input.as_bytes());
+        let (words, stats) = alloc_counter::measure(|| {
+            let mut words = 0;
+            while let Some(line) = reader.next() {
+                words += line.unwrap().split_whitespace().count();
+            }
+            words
+        });
+        assert_eq!(words, 7000);
+        assert_eq!(stats.allocations, 0);
+        assert_eq!(stats.reallocations, 0);
+        assert_eq!(reader.buffer_capacity(), 64);
+    }
+
+    #[test]
+    fn test_buffer_grows_to_longest_line() {
+        fn read_all(input: &str) -> (usize, alloc_counter::AllocStats) {
+            let mut reader = LineReader::new(input.as_bytes());
+            alloc_counter::measure(|| {
+                let mut count = 0;
+                while let Some(line) = reader.next() {
+                    line.unwrap();
+                    count += 1;
+                }
+                count
+            })
+        }
+
+        let block = format!("short\n{}\nshort\n", "x".repeat(500));
+        let (count, few) = read_all(&block);
+        assert_eq!(count, 3);
+        let (count, many) = read_all(&block.repeat(100));
+        assert_eq!(count, 300);
+        // 分配只和最长的一行有关，和行数无关
+        assert_eq!(few.allocations, many.allocations);
+        assert_eq!(few.reallocations, many.reallocations);
+    }
+}
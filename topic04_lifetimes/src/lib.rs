@@ -6,6 +6,8 @@
 // otherwise returns the second string.
 // You need to annotate lifetimes correctly.
 
+// The exercise bodies are kept as written (clippy suggests initializing `ret` directly)
+#[allow(clippy::needless_late_init)]
 pub fn pick_containing<'a>(source: &'a str, pattern: &'a str) -> &'a str {
     // Goal: Implement 'first_if_contains'. Returns x if x contains y. Otherwise returns 🈶.
     let ret : &str;
@@ -20,6 +22,7 @@ pub fn pick_containing<'a>(source: &'a str, pattern: &'a str) -> &'a str {
 // Better Exercise: Just implementing the 'longest' logic again but with a twist?
 // Let's stick to the prompt: "Complement function".
 
+#[allow(clippy::needless_late_init)]
 pub fn longer_term<'a>(s1: &'a str, s2: &'a str) -> &'a str {
     // TODO: Return the string that comes later in the dictionary (lexicographically larger).
     // If equal, return s1.
@@ -33,10 +36,25 @@ pub fn longer_term<'a>(s1: &'a str, s2: &'a str) -> &'a str {
     ret
 }
 
+pub mod document;
+pub mod segment;
+pub mod select;
+pub mod collation;
+pub mod pipeline;
+pub mod split;
+pub mod owned;
+pub mod config;
+pub mod lending;
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // 计数分配器只装在单元测试的二进制里，用来断言 LineReader 不会每行分配；
+    // 依赖 topic04_lifetimes 的程序仍然用默认的分配器。
+    #[global_allocator]
+    static GLOBAL: alloc_counter::CountingAlloc = alloc_counter::CountingAlloc::system();
+
     #[test]
     fn test_pick_containing() {
         assert_eq!(pick_containing("team", "ea"), "team");
@@ -33,23 +33,21 @@ fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
 }
 
 // Structs with references MUST have lifetimes
-struct ImportantExcerpt<'a> {
-    part: &'a str,
-}
+// ImportantExcerpt<'a> 定义在库里 (document.rs)：
+// pub struct ImportantExcerpt<'a> {
+//     pub part: &'a str,
+//     pub start: usize,
+// }
+use topic04_lifetimes::document::{Document, ImportantExcerpt};
 
 fn example_struct() {
     let novel = String::from("Call me Ishmael. Some years ago...");
-    // 1. 获取迭代器 (必须是 mut，因为调用 next() 会改变迭代器内部状态)
-    let mut sentences = novel.split('.');
-    
-    // 2. 第一次调用 next() -> 返回 Some("Call me Ishmael")
-    sentences.next();
-    
-    // 3. 第二次调用 next() -> 返回 Some(" Some years ago")
-    let second_sentence = sentences.next().expect("Could not find a '.'");
-    
-    let _i = ImportantExcerpt {
-        part: second_sentence,
-    };
+    // 1. Document 借用 novel：它和它切出来的所有 excerpt 都不能活得比 novel 久
+    let doc = Document::new(&novel);
+
+    // 2. 按句切分，取第二句 -> "Some years ago..."
+    let second_sentence = doc.sentences()[1];
+
+    let _i: ImportantExcerpt = second_sentence;
     println!("{}", _i.part);
 }
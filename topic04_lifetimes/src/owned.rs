@@ -0,0 +1,167 @@
+// OwnedDocument: 同时拥有原文和摘录
+//
+// ImportantExcerpt<'a> 借用 novel，所以下面两件事都做不到：
+//
+// fn load() -> (String, ImportantExcerpt<'?>)   // 返回值不能借用同一个返回值里的 String
+// struct Cache { novel: String, first: ImportantExcerpt<'?> }  // 自引用结构体
+//
+// 解决办法不是 unsafe 自引用，而是只存“位置”：
+// - OwnedDocument 拥有 String，摘录只记成字节区间 Range<usize>。
+// - 需要用的时候，再把区间变回 ImportantExcerpt<'_>，它借用的是 &self。
+//
+// 这样 OwnedDocument 里没有任何引用，可以随便 move、放进缓存、发送到别的线程 (Send + Sync)。
+
+use std::ops::Range;
+
+use crate::document::{Document, ImportantExcerpt};
+use crate::segment::SentenceSplitter;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnedDocument {
+    text: String,
+    spans: Vec<Range<usize>>,
+}
+
+impl OwnedDocument {
+    /// 拥有 text，并用默认的分句器把句子作为摘录
+    pub fn new(text: impl Into<String>) -> OwnedDocument {
+        OwnedDocument::capture(text, |doc| doc.sentences())
+    }
+
+    /// 用自定义的分句器把句子作为摘录
+    pub fn with_splitter(text: impl Into<String>, splitter: &SentenceSplitter) -> OwnedDocument {
+        OwnedDocument::capture(text, |doc| doc.sentences_with(splitter))
+    }
+
+    /// 用任意的 Document 查询（段落、KWIC、rank……）生成摘录，只保存它们的区间。
+    ///
+    /// 闭包拿到的 Document 借用的是 OwnedDocument 内部的 String，
+    /// 它返回的 excerpt 不能逃出闭包，只有区间会被留下来。
+    pub fn capture<F>(text: impl Into<String>, select: F) -> OwnedDocument
+    where
+        F: for<'d> FnOnce(&Document<'d>) -> Vec<ImportantExcerpt<'d>>,
+    {
+        let text = text.into();
+        let spans = select(&Document::new(&text))
+            .iter()
+            .map(ImportantExcerpt::span)
+            .collect();
+        OwnedDocument { text, spans }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// 借用原文的 Document，用来做新的查询
+    pub fn document(&self) -> Document<'_> {
+        Document::new(&self.text)
+    }
+
+    pub fn spans(&self) -> &[Range<usize>] {
+        &self.spans
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// 第 index 个摘录，借用 &self
+    pub fn get(&self, index: usize) -> Option<ImportantExcerpt<'_>> {
+        self.spans.get(index).map(|span| self.excerpt(span.clone()))
+    }
+
+    pub fn excerpts(&self) -> impl Iterator<Item = ImportantExcerpt<'_>> + '_ {
+        self.spans.iter().map(|span| self.excerpt(span.clone()))
+    }
+
+    pub fn into_text(self) -> String {
+        self.text
+    }
+
+    fn excerpt(&self, span: Range<usize>) -> ImportantExcerpt<'_> {
+        ImportantExcerpt {
+            start: span.start,
+            part: &self.text[span],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::thread;
+
+    // 返回“原文 + 摘录”：用借用的 ImportantExcerpt 是写不出来的
+    fn load(id: u32) -> OwnedDocument {
+        OwnedDocument::new(format!("Chapter {}. Call me Ishmael. Some years ago...", id))
+    }
+
+    #[test]
+    fn test_return_from_function() {
+        let doc = load(1);
+        assert_eq!(doc.len(), 3);
+        assert_eq!(doc.get(1).map(|e| e.part), Some("Call me Ishmael."));
+        assert_eq!(doc.get(3), None);
+        for e in doc.excerpts() {
+            assert_eq!(&doc.text()[e.span()], e.part);
+        }
+    }
+
+    #[test]
+    fn test_cache() {
+        let mut cache: HashMap<u32, OwnedDocument> = HashMap::new();
+        for id in 1..=3 {
+            cache.insert(id, load(id));
+        }
+        let first = cache[&2].get(0).unwrap();
+        assert_eq!(first.part, "Chapter 2.");
+        // 摘录指向缓存里的 String，没有拷贝
+        assert_eq!(first.part.as_ptr(), cache[&2].text().as_ptr());
+    }
+
+    #[test]
+    fn test_capture_any_query() {
+        let text = String::from("The sea. The whale.\n\nA ship on the sea.");
+        let doc = OwnedDocument::capture(text, |d| d.paragraphs());
+        assert_eq!(doc.len(), 2);
+        assert_eq!(doc.get(1).unwrap().part, "A ship on the sea.");
+
+        let hits = OwnedDocument::capture(doc.into_text(), |d| {
+            d.rank("sea").into_iter().map(|r| r.excerpt).collect()
+        });
+        assert_eq!(hits.excerpts().map(|e| e.part).collect::<Vec<_>>(), vec!["The sea.", "A ship on the sea."]);
+        // 仍然可以在原文上做新的查询
+        assert_eq!(hits.document().keyword_in_context("whale", 0)[0].part, "whale");
+    }
+
+    #[test]
+    fn test_with_splitter() {
+        let bare = SentenceSplitter::with_abbreviations([]);
+        assert_eq!(OwnedDocument::new("Dr. Who. Yes.").len(), 2);
+        assert_eq!(OwnedDocument::with_splitter("Dr. Who. Yes.", &bare).len(), 3);
+    }
+
+    #[test]
+    fn test_send_between_threads() {
+        fn assert_send_sync<T: Send + Sync + 'static>() {}
+        assert_send_sync::<OwnedDocument>();
+
+        let handles: Vec<_> = (1..=4)
+            .map(|id| thread::spawn(move || load(id)))
+            .collect();
+        let docs: Vec<OwnedDocument> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        // 再把一个文档 move 到另一个线程里读取摘录
+        let doc = docs.into_iter().nth(3).unwrap();
+        let first = thread::spawn(move || doc.get(0).unwrap().part.to_string())
+            .join()
+            .unwrap();
+        assert_eq!(first, "Chapter 4.");
+    }
+}
@@ -0,0 +1,360 @@
+// Request Pipeline: 三层生命周期的请求处理框架
+//
+// examples/complex_lifetimes.rs 里的三个角色（execute_operation 的 'a: 'b, 'b: 'c），
+// 在这里变成一个框架（用法见 examples/pipeline.rs）：
+// - 'a  Context：全局配置，活得最久（整个程序）。
+// - 'p  Processor：每个工作线程自己的状态，活得比 Context 短 ('a: 'p)。
+// - 请求 Request<'_>：每次调用才创建，活得最短。
+//
+// 规则：Response<'p> 可以借用 Context 或 Processor 里的数据，但绝不能借用请求。
+// 这个规则不靠文档约定，而是写在签名里：
+//
+// fn process<'p>(&'p self, ctx: &'p Context<'a>, req: &Request<'_>, next: Next<'_, 'p, 'a>)
+//     -> Response<'p>;
+//
+// - `&'p Context<'a>` 隐含了 'a: 'p，所以 Context 的 &'a str 可以缩短成 'p 返回。
+// - `&'p self` 让 Processor 自己的数据也可以以 'p 返回。
+// - req 的生命周期是匿名的 '_，和 'p 没有任何关系，试图返回 req.body 会编译失败。
+//   需要请求里的数据时，只能拷贝一份 (Cow::Owned)。
+//
+// 多个 Processor 像中间件一样串成链：每个 Processor 可以自己处理请求并返回，
+// 也可以调用 next.run(req) 交给下一个，再对结果做加工。
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+
+/// 全局上下文：名字和一组只读配置，全部借用自更长寿的数据
+#[derive(Debug, Clone, Default)]
+pub struct Context<'a> {
+    pub name: &'a str,
+    settings: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Context<'a> {
+    pub fn new(name: &'a str) -> Context<'a> {
+        Context {
+            name,
+            settings: Vec::new(),
+        }
+    }
+
+    pub fn with_setting(mut self, key: &'a str, value: &'a str) -> Context<'a> {
+        self.settings.push((key, value));
+        self
+    }
+
+    /// 返回值借用的是 Context 的数据 ('a)，和 key 的生命周期无关
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.settings
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| *v)
+    }
+}
+
+/// 短命的请求
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Request<'c> {
+    pub path: &'c str,
+    pub body: &'c str,
+}
+
+impl<'c> Request<'c> {
+    pub fn new(path: &'c str, body: &'c str) -> Request<'c> {
+        Request { path, body }
+    }
+}
+
+/// 响应：body 可以借用 Context / Processor ('r)，也可以是自己拥有的 String
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response<'r> {
+    pub status: u16,
+    pub body: Cow<'r, str>,
+    /// 经手的 Processor 名字，按处理顺序
+    pub handled_by: Vec<&'r str>,
+}
+
+impl<'r> Response<'r> {
+    pub fn ok(body: impl Into<Cow<'r, str>>) -> Response<'r> {
+        Response {
+            status: 200,
+            body: body.into(),
+            handled_by: Vec::new(),
+        }
+    }
+
+    pub fn not_found() -> Response<'r> {
+        Response {
+            status: 404,
+            body: Cow::Borrowed("not found"),
+            handled_by: Vec::new(),
+        }
+    }
+}
+
+/// 一个处理环节（中间件）
+pub trait Processor<'a> {
+    /// 用于 Response::handled_by，借用自 Processor 本身
+    fn name(&self) -> &str;
+
+    fn process<'p>(
+        &'p self,
+        ctx: &'p Context<'a>,
+        req: &Request<'_>,
+        next: Next<'_, 'p, 'a>,
+    ) -> Response<'p>;
+}
+
+/// 链上剩下的 Processor。run() 把请求交给下一个；链走完了返回 404。
+pub struct Next<'n, 'p, 'a> {
+    ctx: &'p Context<'a>,
+    rest: &'n [&'p dyn Processor<'a>],
+}
+
+impl<'n, 'p, 'a> Next<'n, 'p, 'a> {
+    pub fn run(self, req: &Request<'_>) -> Response<'p> {
+        match self.rest.split_first() {
+            None => Response::not_found(),
+            Some((first, rest)) => {
+                let next = Next { ctx: self.ctx, rest };
+                let mut resp = first.process(self.ctx, req, next);
+                // 越靠前的 Processor 越晚返回，插到最前面保持“处理顺序”
+                resp.handled_by.insert(0, first.name());
+                resp
+            }
+        }
+    }
+}
+
+/// 借用一个 Context 和若干 Processor 组成的处理链
+pub struct Pipeline<'p, 'a> {
+    ctx: &'p Context<'a>,
+    processors: Vec<&'p dyn Processor<'a>>,
+}
+
+impl<'p, 'a> Pipeline<'p, 'a> {
+    pub fn new(ctx: &'p Context<'a>) -> Pipeline<'p, 'a> {
+        Pipeline {
+            ctx,
+            processors: Vec::new(),
+        }
+    }
+
+    pub fn with(mut self, processor: &'p dyn Processor<'a>) -> Pipeline<'p, 'a> {
+        self.processors.push(processor);
+        self
+    }
+
+    /// 执行请求。返回的 Response<'p> 可以比 req 活得更久。
+    pub fn execute(&self, req: &Request<'_>) -> Response<'p> {
+        Next {
+            ctx: self.ctx,
+            rest: &self.processors,
+        }
+        .run(req)
+    }
+}
+
+// === 常用的 Processor ===
+
+/// 记录经过的请求路径（拷贝一份，因为请求很快就会死掉），然后交给下一个
+pub struct Logger {
+    pub id: String,
+    log: RefCell<Vec<String>>,
+}
+
+impl Logger {
+    pub fn new(id: &str) -> Logger {
+        Logger {
+            id: String::from(id),
+            log: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub fn entries(&self) -> Vec<String> {
+        self.log.borrow().clone()
+    }
+}
+
+impl<'a> Processor<'a> for Logger {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    fn process<'p>(&'p self, ctx: &'p Context<'a>, req: &Request<'_>, next: Next<'_, 'p, 'a>) -> Response<'p> {
+        self.log.borrow_mut().push(format!("[{}] {}", ctx.name, req.path));
+        next.run(req)
+    }
+}
+
+/// 固定路由：路径匹配时返回 Processor 自己持有的内容（借用 'p，零拷贝）
+pub struct StaticRoute {
+    pub id: String,
+    pub path: String,
+    pub body: String,
+}
+
+impl<'a> Processor<'a> for StaticRoute {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    fn process<'p>(&'p self, _ctx: &'p Context<'a>, req: &Request<'_>, next: Next<'_, 'p, 'a>) -> Response<'p> {
+        if req.path == self.path {
+            Response::ok(self.body.as_str())
+        } else {
+            next.run(req)
+        }
+    }
+}
+
+/// "/config/<key>"：返回 Context 里的配置（借用 'a，缩短为 'p 返回）
+pub struct ConfigRoute {
+    pub id: String,
+}
+
+impl<'a> Processor<'a> for ConfigRoute {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    fn process<'p>(&'p self, ctx: &'p Context<'a>, req: &Request<'_>, next: Next<'_, 'p, 'a>) -> Response<'p> {
+        match req.path.strip_prefix("/config/").map(|key| ctx.get(key)) {
+            Some(Some(value)) => Response::ok(value),
+            Some(None) => Response::not_found(),
+            None => next.run(req),
+        }
+    }
+}
+
+/// "/echo"：把请求体原样返回。请求不能被借用，所以必须拷贝 (Cow::Owned)。
+pub struct Echo {
+    pub id: String,
+}
+
+impl<'a> Processor<'a> for Echo {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    fn process<'p>(&'p self, _ctx: &'p Context<'a>, req: &Request<'_>, next: Next<'_, 'p, 'a>) -> Response<'p> {
+        if req.path == "/echo" {
+            // Response::ok(req.body) 编译不过：req 的生命周期和 'p 无关
+            Response::ok(String::from(req.body))
+        } else {
+            next.run(req)
+        }
+    }
+}
+
+/// 对下游的响应做加工：给 body 加上前缀
+pub struct Prefix {
+    pub id: String,
+    pub prefix: String,
+}
+
+impl<'a> Processor<'a> for Prefix {
+    fn name(&self) -> &str {
+        &self.id
+    }
+
+    fn process<'p>(&'p self, _ctx: &'p Context<'a>, req: &Request<'_>, next: Next<'_, 'p, 'a>) -> Response<'p> {
+        let mut resp = next.run(req);
+        if resp.status == 200 {
+            resp.body = Cow::Owned(format!("{}{}", self.prefix, resp.body));
+        }
+        resp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route(path: &str, body: &str) -> StaticRoute {
+        StaticRoute {
+            id: format!("route{}", path),
+            path: String::from(path),
+            body: String::from(body),
+        }
+    }
+
+    #[test]
+    fn test_borrow_from_processor() {
+        let ctx = Context::new("System Context");
+        let hello = route("/hello", "Hello from Processor A");
+        let pipeline = Pipeline::new(&ctx).with(&hello);
+
+        let resp = {
+            // 请求在这个作用域里死掉，响应却可以带出来
+            let path = String::from("/hello");
+            pipeline.execute(&Request::new(&path, ""))
+        };
+        assert_eq!(resp.status, 200);
+        assert_eq!(resp.body, "Hello from Processor A");
+        // 零拷贝：body 直接指向 Processor 里的 String
+        assert!(matches!(resp.body, Cow::Borrowed(_)));
+        assert_eq!(resp.body.as_ptr(), hello.body.as_ptr());
+        assert_eq!(resp.handled_by, vec!["route/hello"]);
+    }
+
+    #[test]
+    fn test_borrow_from_context() {
+        let config = String::from("v1.2.3");
+        let ctx = Context::new("System Context").with_setting("version", &config);
+        let resp;
+        {
+            // Processor 活得比 Context 短：'a: 'p
+            let config_route = ConfigRoute { id: String::from("config") };
+            let pipeline = Pipeline::new(&ctx).with(&config_route);
+            let r = pipeline.execute(&Request::new("/config/version", ""));
+            assert_eq!(r.body.as_ptr(), config.as_ptr());
+            resp = r.status;
+            assert_eq!(pipeline.execute(&Request::new("/config/missing", "")).status, 404);
+        }
+        assert_eq!(resp, 200);
+    }
+
+    #[test]
+    fn test_middleware_chain() {
+        let ctx = Context::new("ctx");
+        let logger = Logger::new("logger");
+        let prefix = Prefix {
+            id: String::from("prefix"),
+            prefix: String::from(">> "),
+        };
+        let echo = Echo { id: String::from("echo") };
+        let hello = route("/hello", "hi");
+        let pipeline = Pipeline::new(&ctx)
+            .with(&logger)
+            .with(&prefix)
+            .with(&echo)
+            .with(&hello);
+
+        let body = String::from("ping");
+        let resp = pipeline.execute(&Request::new("/echo", &body));
+        drop(body); // 响应里的数据是拷贝出来的，不依赖请求
+        assert_eq!(resp.body, ">> ping");
+        assert_eq!(resp.handled_by, vec!["logger", "prefix", "echo"]);
+
+        let resp = pipeline.execute(&Request::new("/hello", ""));
+        assert_eq!(resp.body, ">> hi");
+        assert_eq!(resp.handled_by, vec!["logger", "prefix", "echo", "route/hello"]);
+
+        let resp = pipeline.execute(&Request::new("/nope", ""));
+        assert_eq!(resp, Response {
+            status: 404,
+            body: Cow::Borrowed("not found"),
+            handled_by: vec!["logger", "prefix", "echo", "route/hello"],
+        });
+
+        assert_eq!(logger.entries(), vec!["[ctx] /echo", "[ctx] /hello", "[ctx] /nope"]);
+    }
+
+    #[test]
+    fn test_empty_pipeline() {
+        let ctx = Context::new("ctx");
+        let pipeline = Pipeline::new(&ctx);
+        assert_eq!(pipeline.execute(&Request::new("/", "")).status, 404);
+    }
+}
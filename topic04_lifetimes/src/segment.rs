@@ -0,0 +1,285 @@
+// Sentence Segmentation: 比 split('.') 更靠谱的分句
+//
+// example_struct 最初按每个 '.' 切分：
+// "Dr. Smith paid 3.14 dollars..." 会被切成 "Dr" / " Smith paid 3" / "14 dollars" / "" / ""。
+//
+// SentenceSplitter 的规则：
+// 1. 句末标点：英文 `. ! ?` 和中文 `。 ！ ？`，连续的标点 ("?!"、"...") 算一组。
+// 2. 句末标点后面可以跟着闭合的引号/括号 (`" ' ” ’ ) 」 』 ）`)，它们属于前一句。
+// 3. 英文标点后面必须是空白或文本结尾，所以 "3.14"、"example.com" 不会被切开；
+//    中文标点后面不需要空格。
+// 4. 单个 '.' 前面是缩写 ("Dr"、"e.g"，可配置) 或单个大写字母 ("J. Smith") 时不切。
+// 5. 省略号 ("...") 后面如果接的是小写字母，视为句子还没结束。
+//
+// 返回的 Sentence<'a> 是原文的切片（去掉了首尾空白）以及它在原文中的字节区间。
+
+use std::ops::Range;
+
+/// 一个句子：借用自原文
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sentence<'a> {
+    pub text: &'a str,
+    /// text 在原文中的起始字节位置
+    pub start: usize,
+}
+
+impl<'a> Sentence<'a> {
+    pub fn span(&self) -> Range<usize> {
+        self.start..self.start + self.text.len()
+    }
+}
+
+const DEFAULT_ABBREVIATIONS: &[&str] = &[
+    "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "vs", "etc", "e.g", "i.e", "no", "fig",
+    "inc", "ltd", "co", "jan", "feb", "mar", "apr", "jun", "jul", "aug", "sep", "oct", "nov",
+    "dec", "u.s", "a.m", "p.m",
+];
+
+fn is_terminator(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '。' | '！' | '？' | '…')
+}
+
+fn is_cjk_terminator(c: char) -> bool {
+    matches!(c, '。' | '！' | '？')
+}
+
+fn is_closing(c: char) -> bool {
+    matches!(c, '"' | '\'' | '”' | '’' | ')' | ']' | '」' | '』' | '）')
+}
+
+fn is_opening(c: char) -> bool {
+    matches!(c, '"' | '\'' | '“' | '‘' | '(' | '[' | '「' | '『' | '（')
+}
+
+pub struct SentenceSplitter {
+    // 统一存成小写，比较时忽略 ASCII 大小写
+    abbreviations: Vec<String>,
+}
+
+impl Default for SentenceSplitter {
+    /// 带有常见英文缩写表的分句器
+    fn default() -> SentenceSplitter {
+        SentenceSplitter::with_abbreviations(DEFAULT_ABBREVIATIONS.iter().copied())
+    }
+}
+
+impl SentenceSplitter {
+    pub fn new() -> SentenceSplitter {
+        SentenceSplitter::default()
+    }
+
+    /// 只使用给定的缩写表（不含默认表）。缩写不带最后的 '.'，例如 "Dr"、"e.g"。
+    pub fn with_abbreviations<'s>(abbreviations: impl IntoIterator<Item = &'s str>) -> SentenceSplitter {
+        let mut splitter = SentenceSplitter {
+            abbreviations: Vec::new(),
+        };
+        for abbr in abbreviations {
+            splitter.add_abbreviation(abbr);
+        }
+        splitter
+    }
+
+    pub fn add_abbreviation(&mut self, abbr: &str) {
+        let abbr = abbr.trim_end_matches('.').to_ascii_lowercase();
+        if !abbr.is_empty() && !self.abbreviations.contains(&abbr) {
+            self.abbreviations.push(abbr);
+        }
+    }
+
+    pub fn is_abbreviation(&self, word: &str) -> bool {
+        self.abbreviations.iter().any(|a| a.eq_ignore_ascii_case(word))
+    }
+
+    /// 切分句子。返回值只借用 text，与 &self 的生命周期无关。
+    pub fn split<'a>(&self, text: &'a str) -> Vec<Sentence<'a>> {
+        let mut out = Vec::new();
+        let mut start = 0;
+        let mut chars = text.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            if !is_terminator(c) {
+                continue;
+            }
+
+            // 1. 吃掉连续的句末标点
+            let mut run = 1;
+            let mut cjk = is_cjk_terminator(c);
+            let mut end = i + c.len_utf8();
+            while let Some(&(j, d)) = chars.peek() {
+                if !is_terminator(d) {
+                    break;
+                }
+                run += 1;
+                cjk |= is_cjk_terminator(d);
+                end = j + d.len_utf8();
+                chars.next();
+            }
+
+            // 2. 吃掉紧跟的闭合引号/括号
+            while let Some(&(j, d)) = chars.peek() {
+                if !is_closing(d) {
+                    break;
+                }
+                end = j + d.len_utf8();
+                chars.next();
+            }
+
+            // 中文标点：不需要后面有空格，直接断句
+            if cjk || self.ends_sentence(text, start..i, c, run, end) {
+                push_trimmed(&mut out, text, start, end);
+                start = end;
+            }
+        }
+        push_trimmed(&mut out, text, start, text.len());
+        out
+    }
+
+    // 英文句末标点 c（共 run 个）是否结束了句子。
+    // body: 当前句子在标点之前的部分；end: 标点和闭合符号之后的位置
+    fn ends_sentence(&self, text: &str, body: Range<usize>, c: char, run: usize, end: usize) -> bool {
+        let rest = &text[end..];
+        let next = rest.chars().next();
+
+        // 英文标点：后面必须是空白或结尾
+        if next.is_some_and(|n| !n.is_whitespace()) {
+            return false;
+        }
+
+        let next_word = rest.trim_start().chars().find(|ch| !is_opening(*ch));
+        let ellipsis = c == '…' || (c == '.' && run >= 3);
+        if ellipsis {
+            // "Wait... what" 还是同一句；"Wait... What?" 是新的一句
+            return !next_word.is_some_and(char::is_lowercase);
+        }
+
+        if c == '.' && run == 1 {
+            // 取 '.' 前面的那个词（去掉开头的引号/括号）
+            let before = &text[body];
+            let word = before
+                .rsplit(char::is_whitespace)
+                .next()
+                .unwrap_or("")
+                .trim_start_matches(is_opening);
+            if self.is_abbreviation(word) {
+                return false;
+            }
+            // 单个大写字母的缩写，如 "J. R. R. Tolkien"
+            let mut wc = word.chars();
+            if let (Some(first), None) = (wc.next(), wc.next()) {
+                if first.is_uppercase() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+fn push_trimmed<'a>(out: &mut Vec<Sentence<'a>>, text: &'a str, start: usize, end: usize) {
+    let raw = &text[start..end];
+    let trimmed = raw.trim();
+    if !trimmed.is_empty() {
+        out.push(Sentence {
+            text: trimmed,
+            start: start + (raw.len() - raw.trim_start().len()),
+        });
+    }
+}
+
+/// 使用默认缩写表分句
+pub fn split_sentences(text: &str) -> Vec<Sentence<'_>> {
+    SentenceSplitter::default().split(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(sentences: &[Sentence<'a>]) -> Vec<&'a str> {
+        sentences.iter().map(|s| s.text).collect()
+    }
+
+    #[test]
+    fn test_basic_english() {
+        let text = "Call me Ishmael. Some years ago... never mind how long. Why? Because!";
+        assert_eq!(
+            texts(&split_sentences(text)),
+            vec!["Call me Ishmael.", "Some years ago... never mind how long.", "Why?", "Because!"]
+        );
+    }
+
+    #[test]
+    fn test_abbreviations_and_decimals() {
+        let text = "Dr. Smith paid 3.14 dollars, e.g. for coffee at 9 a.m. on Main St. today. J. R. R. Tolkien agreed.";
+        assert_eq!(
+            texts(&split_sentences(text)),
+            vec![
+                "Dr. Smith paid 3.14 dollars, e.g. for coffee at 9 a.m. on Main St. today.",
+                "J. R. R. Tolkien agreed.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_configurable_abbreviations() {
+        let text = "See Appx. B for details. Thanks.";
+        assert_eq!(split_sentences(text).len(), 3);
+
+        let mut splitter = SentenceSplitter::new();
+        splitter.add_abbreviation("appx.");
+        assert_eq!(splitter.split(text).len(), 2);
+
+        // 空的缩写表："Dr." 也会被当成句末
+        let bare = SentenceSplitter::with_abbreviations([]);
+        assert_eq!(bare.split("Dr. Who. Yes.").len(), 3);
+    }
+
+    #[test]
+    fn test_quotes_and_ellipsis() {
+        let text = "He said \"Stop!\" Then he left... What happened? (Nobody knew.) The end";
+        assert_eq!(
+            texts(&split_sentences(text)),
+            vec![
+                "He said \"Stop!\"",
+                "Then he left...",
+                "What happened?",
+                "(Nobody knew.)",
+                "The end",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chinese_punctuation() {
+        let text = "你好。今天天气不错！你去哪儿？他说：“走吧。”然后离开了。";
+        assert_eq!(
+            texts(&split_sentences(text)),
+            vec!["你好。", "今天天气不错！", "你去哪儿？", "他说：“走吧。”", "然后离开了。"]
+        );
+
+        // 连续的中文标点算一组
+        assert_eq!(texts(&split_sentences("真的吗？！是的。")), vec!["真的吗？！", "是的。"]);
+    }
+
+    #[test]
+    fn test_spans_point_into_source() {
+        let text = String::from("  First one.   Second one!\n第三句。");
+        let sentences = split_sentences(&text);
+        assert_eq!(sentences.len(), 3);
+        for s in &sentences {
+            assert_eq!(&text[s.span()], s.text);
+        }
+        assert_eq!(sentences[0].start, 2);
+    }
+
+    #[test]
+    fn test_splitter_lifetime_independent() {
+        // 分句器先于结果被丢掉：返回值只借用 text
+        let text = "A b. C d.";
+        let sentences = {
+            let splitter = SentenceSplitter::new();
+            splitter.split(text)
+        };
+        assert_eq!(sentences.len(), 2);
+    }
+}
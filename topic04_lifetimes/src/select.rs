@@ -0,0 +1,211 @@
+// Generic Selection: 从任意多个借用中挑出一个
+//
+// `longest`、`longer_term`、`pick_containing` 都是同一个模式：
+// 给两个 &'a str，按某种规则返回其中一个，返回值的生命周期也是 'a。
+//
+// longest(x, y)            == select_with(&[x, y], by_len, Ties::Last)
+// longer_term(s1, s2)      == select_with(&[s1, s2], str::cmp, Ties::First)
+// pick_containing(src, p)  == select_where(&[src, p], |s| s.contains(p))
+//
+// 这里把它推广到任意个数、任意类型 (T: ?Sized，所以 str、[u8] 也可以)：
+// - 输入是 &[&'a T]，输出是 Option<&'a T>：切片为空时返回 None。
+// - 返回值的生命周期只和元素的 'a 有关，和切片本身、比较器都无关。
+// - 比较器是可插拔的 Fn(&T, &T) -> Ordering，下面的 cmp 模块提供了常用的几种。
+
+use std::cmp::Ordering;
+
+/// 出现“并列最大”时选哪一个
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ties {
+    /// 选最先出现的（longer_term 的规则）
+    #[default]
+    First,
+    /// 选最后出现的（longest 的规则）
+    Last,
+}
+
+/// 用比较器选出“最大”的元素
+pub fn select_with<'a, T, F>(items: &[&'a T], mut cmp: F, ties: Ties) -> Option<&'a T>
+where
+    T: ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let (first, rest) = items.split_first()?;
+    let mut best: &'a T = first;
+    for &item in rest {
+        let replace = match cmp(item, best) {
+            Ordering::Greater => true,
+            Ordering::Equal => ties == Ties::Last,
+            Ordering::Less => false,
+        };
+        if replace {
+            best = item;
+        }
+    }
+    Some(best)
+}
+
+/// 按 key 选出最大的元素，并列时取第一个
+pub fn select_by<'a, T, K, F>(items: &[&'a T], mut key: F) -> Option<&'a T>
+where
+    T: ?Sized,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    select_with(items, |a, b| key(a).cmp(&key(b)), Ties::First)
+}
+
+/// 按 key 选出最小的元素，并列时取第一个
+pub fn select_min_by<'a, T, K, F>(items: &[&'a T], mut key: F) -> Option<&'a T>
+where
+    T: ?Sized,
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    select_with(items, |a, b| key(b).cmp(&key(a)), Ties::First)
+}
+
+/// 选出第一个满足条件的元素
+pub fn select_where<'a, T, P>(items: &[&'a T], mut pred: P) -> Option<&'a T>
+where
+    T: ?Sized,
+    P: FnMut(&T) -> bool,
+{
+    items.iter().copied().find(|item| pred(item))
+}
+
+/// 按比较器从大到小排序。排序是稳定的：相等的元素保持原来的相对顺序。
+pub fn ranked_with<'a, T, F>(items: &[&'a T], mut cmp: F) -> Vec<&'a T>
+where
+    T: ?Sized,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut out = items.to_vec();
+    out.sort_by(|a, b| cmp(b, a));
+    out
+}
+
+/// 可插拔的比较器
+pub mod cmp {
+    use std::cmp::Ordering;
+
+    /// 按字节长度比较
+    pub fn by_len(a: &str, b: &str) -> Ordering {
+        a.len().cmp(&b.len())
+    }
+
+    /// 按字符个数比较（"你好" 是 2 个字符，6 个字节）
+    pub fn by_char_count(a: &str, b: &str) -> Ordering {
+        a.chars().count().cmp(&b.chars().count())
+    }
+
+    /// 忽略大小写的字典序（即 collation::cmp_case_insensitive）。
+    /// 忽略大小写后相等时，再按原始字节序区分，保证结果是全序。
+    pub fn case_insensitive(a: &str, b: &str) -> Ordering {
+        crate::collation::cmp_case_insensitive(a, b).then_with(|| a.cmp(b))
+    }
+
+    /// 反转一个比较器：把“选最大”变成“选最小”
+    pub fn reverse<T: ?Sized>(mut cmp: impl FnMut(&T, &T) -> Ordering) -> impl FnMut(&T, &T) -> Ordering {
+        move |a, b| cmp(b, a)
+    }
+
+    /// 组合两个比较器：先按 first，相等时再按 second
+    pub fn then<T: ?Sized>(
+        mut first: impl FnMut(&T, &T) -> Ordering,
+        mut second: impl FnMut(&T, &T) -> Ordering,
+    ) -> impl FnMut(&T, &T) -> Ordering {
+        move |a, b| first(a, b).then_with(|| second(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cmp::*;
+    use super::*;
+    use crate::{longer_term, pick_containing};
+
+    #[test]
+    fn test_generalizes_two_item_functions() {
+        let pairs = [("Apple", "Banana"), ("Zebra", "Ant"), ("Equal", "Equal"), ("", "NonEmpty"), ("team", "ea")];
+        for (a, b) in pairs {
+            assert_eq!(select_with(&[a, b], str::cmp, Ties::First), Some(longer_term(a, b)));
+            assert_eq!(select_where(&[a, b], |s| s.contains(b)), Some(pick_containing(a, b)));
+        }
+
+        // longest：长度相同时返回第二个
+        assert_eq!(select_with(&["abc", "xyz"], by_len, Ties::Last), Some("xyz"));
+        assert_eq!(select_with(&["abc", "xyz"], by_len, Ties::First), Some("abc"));
+    }
+
+    #[test]
+    fn test_any_number_of_items() {
+        let words = ["pear", "fig", "banana", "kiwi", "cherry"];
+        let items: Vec<&str> = words.to_vec();
+        assert_eq!(select_by(&items, str::len), Some("banana"));
+        assert_eq!(select_min_by(&items, str::len), Some("fig"));
+        assert_eq!(select_with(&items, by_len, Ties::Last), Some("cherry"));
+        assert_eq!(select_by::<str, usize, _>(&[], str::len), None);
+    }
+
+    #[test]
+    fn test_result_outlives_slice_and_comparator() {
+        let a = String::from("long string is long");
+        let result;
+        {
+            // 切片和闭包都在这个作用域里死掉，但结果借用的是 a
+            let b = "xyz";
+            let items = vec![a.as_str(), b];
+            let cmp = |x: &str, y: &str| by_len(x, y);
+            result = select_with(&items, cmp, Ties::First);
+        }
+        assert_eq!(result, Some("long string is long"));
+    }
+
+    #[test]
+    fn test_non_str_items() {
+        #[derive(Debug, PartialEq)]
+        struct Player {
+            name: &'static str,
+            score: u32,
+        }
+        let (a, b, c) = (
+            Player { name: "a", score: 10 },
+            Player { name: "b", score: 30 },
+            Player { name: "c", score: 30 },
+        );
+        let players = [&a, &b, &c];
+        assert_eq!(select_by(&players, |p| p.score).map(|p| p.name), Some("b"));
+
+        // 切片 (DST) 也可以
+        let slices: [&[i32]; 2] = [&[1, 2, 3], &[4]];
+        assert_eq!(select_by(&slices, |s| s.iter().sum::<i32>()), Some(&[1, 2, 3][..]));
+    }
+
+    #[test]
+    fn test_case_insensitive() {
+        // 字节序：'B' (66) < 'a' (97)，所以 "apple" 比 "Banana" 大
+        assert_eq!(longer_term("apple", "Banana"), "apple");
+        assert_eq!(select_with(&["apple", "Banana"], case_insensitive, Ties::First), Some("Banana"));
+        assert_eq!(select_with(&["ÉCOLE", "école"], case_insensitive, Ties::First), Some("école"));
+    }
+
+    #[test]
+    fn test_ranked_is_stable() {
+        let items = ["bb", "a", "cc", "d", "eee"];
+        let ranked = ranked_with(&items, by_len);
+        assert_eq!(ranked, vec!["eee", "bb", "cc", "a", "d"]);
+
+        let ranked = ranked_with(&items, reverse(by_len));
+        assert_eq!(ranked, vec!["a", "d", "bb", "cc", "eee"]);
+
+        let ranked = ranked_with(&items, then(by_len, reverse(str::cmp)));
+        assert_eq!(ranked, vec!["eee", "bb", "cc", "a", "d"]);
+    }
+
+    #[test]
+    fn test_char_count() {
+        assert_eq!(select_by(&["你好", "abcd"], str::len), Some("你好"));
+        assert_eq!(select_with(&["你好", "abcd"], by_char_count, Ties::First), Some("abcd"));
+    }
+}
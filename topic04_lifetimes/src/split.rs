@@ -0,0 +1,265 @@
+// Split: 按分隔符切分，返回值只和原文有关
+//
+// examples/three_lifetimes.rs 里的 extract_prefix 展示了一个关键点：
+// 分隔符只用来查找，不会出现在返回值里，所以它的生命周期可以比原文短。
+//
+// fn extract_prefix<'a>(text: &'a str, delimiter: &str) -> &'a str
+//
+// 这里的所有函数都遵守同一个规则：
+// - 原文是 &'a str，返回的切片（以及 Splitter 产生的每一项）都是 &'a str。
+// - 分隔符是匿名生命周期，调用完就可以丢掉；Splitter 会借用分隔符 ('d)，
+//   但它产生的切片仍然只借用原文，迭代器死了切片还能用。
+// - 空分隔符和 str::split_once / str::split 一样，在每个字符边界都匹配：
+//   extract_prefix(text, "") == ""，和 examples 里原来的 text.find("") 一致。
+//
+// 多个分隔符时只从左到右扫一遍：在每个位置同时检查所有分隔符。
+// 不会为每个分隔符各扫一遍剩余文本，所以某个分隔符根本不出现时也不会退化成 O(n²)。
+
+/// 在第一个 delimiter 处切成 (前, 后)，不包含 delimiter 本身
+pub fn split_once_borrowed<'a>(text: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    let at = text.find(delimiter)?;
+    Some((&text[..at], &text[at + delimiter.len()..]))
+}
+
+/// 在最后一个 delimiter 处切成 (前, 后)
+pub fn rsplit_once_borrowed<'a>(text: &'a str, delimiter: &str) -> Option<(&'a str, &'a str)> {
+    let at = text.rfind(delimiter)?;
+    Some((&text[..at], &text[at + delimiter.len()..]))
+}
+
+/// 第一个 delimiter 之前的部分；找不到时返回整个 text
+pub fn extract_prefix<'a>(text: &'a str, delimiter: &str) -> &'a str {
+    split_once_borrowed(text, delimiter).map_or(text, |(prefix, _)| prefix)
+}
+
+/// 最后一个 delimiter 之前的部分；找不到时返回整个 text
+pub fn rsplit_prefix<'a>(text: &'a str, delimiter: &str) -> &'a str {
+    rsplit_once_borrowed(text, delimiter).map_or(text, |(prefix, _)| prefix)
+}
+
+/// start 之后、紧接着的 end 之前的部分，如 between("f(x, y)", "(", ")") == Some("x, y")
+pub fn between<'a>(text: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = split_once_borrowed(text, start)?;
+    let (inner, _) = split_once_borrowed(rest, end)?;
+    Some(inner)
+}
+
+/// 从字节位置 `from` 开始，找最早出现的分隔符：返回 (位置, 长度)。
+/// 同一位置有多个分隔符匹配时取最长的，所以 ["\r\n", "\n"] 的顺序无关紧要。
+/// 一遍扫描：每个位置检查所有分隔符，找到第一个匹配就停。
+fn find_any(text: &str, from: usize, delimiters: &[&str]) -> Option<(usize, usize)> {
+    let positions = text
+        .get(from..)?
+        .char_indices()
+        .map(|(i, _)| from + i)
+        // 末尾也是一个字符边界（空分隔符可以在这里匹配）
+        .chain(std::iter::once(text.len()));
+    for at in positions {
+        let rest = &text[at..];
+        let longest = delimiters.iter().filter(|d| rest.starts_with(**d)).map(|d| d.len()).max();
+        if let Some(len) = longest {
+            return Some((at, len));
+        }
+    }
+    None
+}
+
+/// 在最早出现的任意一个分隔符处切开：返回 (前, 匹配到的分隔符, 后)。
+/// 匹配到的分隔符也是原文的切片。
+pub fn split_once_any<'a>(text: &'a str, delimiters: &[&str]) -> Option<(&'a str, &'a str, &'a str)> {
+    let (at, len) = find_any(text, 0, delimiters)?;
+    Some((&text[..at], &text[at..at + len], &text[at + len..]))
+}
+
+/// 任意一个分隔符之前的部分；都找不到时返回整个 text
+pub fn extract_prefix_any<'a>(text: &'a str, delimiters: &[&str]) -> &'a str {
+    split_once_any(text, delimiters).map_or(text, |(prefix, _, _)| prefix)
+}
+
+/// 按一组分隔符切分的迭代器
+///
+/// 'a 是原文，'d 是分隔符。Item 是 &'a str，和 'd 无关。
+/// 只有空分隔符时和 str::split("") 一样：在每个字符边界切开。
+#[derive(Debug, Clone)]
+pub struct Splitter<'a, 'd> {
+    text: &'a str,
+    // 当前片段的起点
+    start: usize,
+    // 下一次从这里开始找分隔符；整个迭代过程中只会往前走
+    search: usize,
+    finished: bool,
+    delimiters: &'d [&'d str],
+    skip_empty: bool,
+}
+
+impl<'a, 'd> Splitter<'a, 'd> {
+    pub fn new(text: &'a str, delimiters: &'d [&'d str]) -> Splitter<'a, 'd> {
+        Splitter {
+            text,
+            start: 0,
+            search: 0,
+            finished: false,
+            delimiters,
+            skip_empty: false,
+        }
+    }
+
+    /// 跳过空片段（连续分隔符、开头或结尾的分隔符产生的 ""）
+    pub fn skip_empty(mut self) -> Splitter<'a, 'd> {
+        self.skip_empty = true;
+        self
+    }
+
+    /// 还没有被切分的剩余部分
+    pub fn remainder(&self) -> Option<&'a str> {
+        if self.finished {
+            None
+        } else {
+            Some(&self.text[self.start..])
+        }
+    }
+}
+
+impl<'a, 'd> Iterator for Splitter<'a, 'd> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        loop {
+            if self.finished {
+                return None;
+            }
+            let piece = match find_any(self.text, self.search, self.delimiters) {
+                Some((at, len)) => {
+                    let piece = &self.text[self.start..at];
+                    self.start = at + len;
+                    // 空分隔符匹配后至少前进一个字符，否则会在同一位置一直匹配下去
+                    let step = if len == 0 {
+                        self.text[at..].chars().next().map_or(1, char::len_utf8)
+                    } else {
+                        len
+                    };
+                    self.search = at + step;
+                    piece
+                }
+                None => {
+                    self.finished = true;
+                    &self.text[self.start..]
+                }
+            };
+            if !(self.skip_empty && piece.is_empty()) {
+                return Some(piece);
+            }
+        }
+    }
+}
+
+/// 按一组分隔符切分
+pub fn split_any<'a, 'd>(text: &'a str, delimiters: &'d [&'d str]) -> Splitter<'a, 'd> {
+    Splitter::new(text, delimiters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_delimiter() {
+        let text = "key=value=more";
+        assert_eq!(split_once_borrowed(text, "="), Some(("key", "value=more")));
+        assert_eq!(rsplit_once_borrowed(text, "="), Some(("key=value", "more")));
+        assert_eq!(extract_prefix(text, "="), "key");
+        assert_eq!(rsplit_prefix(text, "="), "key=value");
+
+        assert_eq!(split_once_borrowed(text, ":"), None);
+        assert_eq!(extract_prefix(text, ":"), text);
+        assert_eq!(rsplit_prefix(text, ":"), text);
+
+        // 空分隔符在开头就匹配，和 str::split_once 以及原来 examples 里的 text.find("") 一致
+        assert_eq!(split_once_borrowed(text, ""), text.split_once(""));
+        assert_eq!(split_once_borrowed(text, ""), Some(("", text)));
+        assert_eq!(extract_prefix(text, ""), "");
+        assert_eq!(rsplit_once_borrowed(text, ""), text.rsplit_once(""));
+        assert_eq!(rsplit_prefix(text, ""), text);
+    }
+
+    #[test]
+    fn test_between() {
+        assert_eq!(between("f(x, y) + g(z)", "(", ")"), Some("x, y"));
+        assert_eq!(between("<a><b>", "<", ">"), Some("a"));
+        assert_eq!(between("no brackets", "[", "]"), None);
+        assert_eq!(between("[unclosed", "[", "]"), None);
+        assert_eq!(between("【标题】正文", "【", "】"), Some("标题"));
+    }
+
+    #[test]
+    fn test_multi_delimiter() {
+        let text = "a,b;c";
+        assert_eq!(split_once_any(text, &[";", ","]), Some(("a", ",", "b;c")));
+        assert_eq!(extract_prefix_any(text, &[";", ","]), "a");
+        assert_eq!(extract_prefix_any(text, &["|"]), text);
+
+        // 同一位置取最长的分隔符，和顺序无关
+        assert_eq!(split_once_any("x\r\ny", &["\n", "\r\n"]), Some(("x", "\r\n", "y")));
+        assert_eq!(split_once_any("x\r\ny", &["\r\n", "\n"]), Some(("x", "\r\n", "y")));
+
+        // 和单分隔符版本一样，空分隔符在开头就匹配
+        assert_eq!(split_once_any(text, &["", ","]), Some(("", "", text)));
+        assert_eq!(extract_prefix_any(text, &[""]), extract_prefix(text, ""));
+    }
+
+    #[test]
+    fn test_single_scan() {
+        // 一个从不出现的分隔符不会让每次 next() 都重新扫描整个剩余文本
+        let text = "a,".repeat(50_000);
+        let count = split_any(&text, &["never", ","]).count();
+        assert_eq!(count, 50_001);
+
+        let mut it = split_any("a;b,c", &["never", ",", ";"]);
+        assert_eq!(it.next(), Some("a"));
+        assert_eq!(it.remainder(), Some("b,c"));
+        assert_eq!(it.by_ref().collect::<Vec<_>>(), vec!["b", "c"]);
+        assert_eq!(it.remainder(), None);
+    }
+
+    #[test]
+    fn test_splitter() {
+        let parts: Vec<&str> = split_any("a, b;;c", &[",", ";"]).collect();
+        assert_eq!(parts, vec!["a", " b", "", "c"]);
+
+        let parts: Vec<&str> = split_any(";a;;b;", &[";"]).skip_empty().collect();
+        assert_eq!(parts, vec!["a", "b"]);
+
+        // 和 str::split 一致：结尾的分隔符会产生一个空片段
+        let parts: Vec<&str> = split_any("a;", &[";"]).collect();
+        assert_eq!(parts, "a;".split(';').collect::<Vec<_>>());
+
+        let mut it = split_any("one two three", &[" "]);
+        assert_eq!(it.next(), Some("one"));
+        assert_eq!(it.remainder(), Some("two three"));
+
+        // 只有空分隔符：和 str::split("") 一样，在每个字符边界切开
+        assert_eq!(split_any("abc", &[""]).collect::<Vec<_>>(), "abc".split("").collect::<Vec<_>>());
+        assert_eq!(split_any("你好", &[""]).collect::<Vec<_>>(), vec!["", "你", "好", ""]);
+        assert_eq!(split_any("a,b", &["", ","]).skip_empty().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(split_any("", &[","]).collect::<Vec<_>>(), vec![""]);
+    }
+
+    #[test]
+    fn test_results_outlive_delimiters() {
+        let text = String::from("The quick brown fox jumps over the lazy dog");
+        let (prefix, middle, words);
+        {
+            let delimiter = String::from("fox");
+            let delimiters = [String::from(" "), String::from("fox")];
+            let refs: Vec<&str> = delimiters.iter().map(String::as_str).collect();
+
+            prefix = extract_prefix(&text, &delimiter);
+            middle = between(&text, &delimiters[1], "lazy");
+            words = split_any(&text, &refs).skip_empty().collect::<Vec<_>>();
+        } // 分隔符和 Splitter 都在这里死掉
+        assert_eq!(prefix, "The quick brown ");
+        assert_eq!(middle, Some(" jumps over the "));
+        assert_eq!(words.len(), 8);
+        assert_eq!(words[3], "jumps");
+    }
+}
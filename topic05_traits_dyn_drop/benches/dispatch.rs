@@ -0,0 +1,122 @@
+// Static vs dynamic dispatch benchmark
+//
+// cargo bench -p topic05_traits_dyn_drop
+//
+// No external crates (criterion needs network access): each case runs ROUNDS times
+// over N items and the fastest round is reported. std::hint::black_box keeps the
+// optimizer from deleting the work or specializing on the input.
+//
+// Cases, all computing the total length of summarize() over the same content:
+// 1. impl Summary     - one Vec per concrete type, generic function (monomorphized, inlinable)
+// 2. &dyn Summary     - Vec of fat references into those Vecs (vtable call, no extra allocation)
+// 3. Box<dyn Summary> - Vec of boxes (vtable call + one heap object per item, scattered in memory)
+// 4. Content         - Vec of the closed enum (match, items stored inline)
+//
+// summarize() allocates a String, so allocation dominates every case; the differences
+// between the rows are the dispatch and memory-layout costs on top of that.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use topic05_traits_dyn_drop::content::Content;
+use topic05_traits_dyn_drop::dispatch;
+use topic05_traits_dyn_drop::{NewsArticle, Summary, Tweet, Wechat};
+
+const N: usize = 30_000;
+const ROUNDS: usize = 7;
+
+fn bench(name: &str, mut f: impl FnMut() -> usize) {
+    let mut best = Duration::MAX;
+    let mut result = 0;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        result = black_box(f());
+        best = best.min(start.elapsed());
+    }
+    println!(
+        "{:<20} {:>10.2?}  {:>7.1} ns/item  (checksum {})",
+        name,
+        best,
+        best.as_nanos() as f64 / N as f64,
+        result
+    );
+}
+
+/// `n` items cycling news -> tweet -> wechat, each numbered with its index so no two
+/// summaries are equal. Some tweets are replies or retweets.
+fn mixed(n: usize) -> Vec<Content> {
+    (0..n)
+        .map(|i| match i % 3 {
+            0 => Content::News(NewsArticle {
+                headline: format!("Headline number {}", i),
+                location: String::from("Pittsburgh, PA, USA"),
+                author: String::from("Iceburgh"),
+                content: String::new(),
+            }),
+            1 => Content::Tweet(Tweet {
+                username: String::from("horse_ebooks"),
+                content: format!("tweet number {}", i),
+                reply: i % 5 == 0,
+                retweet: i % 7 == 0,
+            }),
+            _ => Content::Wechat(Wechat {
+                official_account: String::from("RustLang"),
+                title: format!("post number {}", i),
+            }),
+        })
+        .collect()
+}
+
+#[inline(never)]
+fn total_static<T: Summary>(items: &[T]) -> usize {
+    items.iter().map(|i| black_box(i).summarize().len()).sum()
+}
+
+#[inline(never)]
+fn total_dyn_ref(items: &[&dyn Summary]) -> usize {
+    items.iter().map(|i| black_box(*i).summarize().len()).sum()
+}
+
+#[inline(never)]
+fn total_boxed(items: &[Box<dyn Summary>]) -> usize {
+    items.iter().map(|i| black_box(&**i).summarize().len()).sum()
+}
+
+#[inline(never)]
+fn total_enum(items: &[Content]) -> usize {
+    items.iter().map(|i| black_box(i).summarize().len()).sum()
+}
+
+fn main() {
+    // The same N items in every representation, cycling news -> tweet -> wechat
+    let mut news: Vec<NewsArticle> = Vec::new();
+    let mut tweets: Vec<Tweet> = Vec::new();
+    let mut wechats: Vec<Wechat> = Vec::new();
+    for item in mixed(N) {
+        match item {
+            Content::News(a) => news.push(a),
+            Content::Tweet(t) => tweets.push(t),
+            Content::Wechat(w) => wechats.push(w),
+        }
+    }
+
+    let mut refs: Vec<&dyn Summary> = Vec::with_capacity(N);
+    for i in 0..N / 3 {
+        refs.push(&news[i]);
+        refs.push(&tweets[i]);
+        refs.push(&wechats[i]);
+    }
+
+    let boxed: Vec<Box<dyn Summary>> = mixed(N).into_iter().map(Box::from).collect();
+    let enums: Vec<Content> = mixed(N);
+
+    println!("--- Sizes ---");
+    print!("{}", dispatch::report(&boxed[..3]));
+    println!("{:<28} {:>6}", "Content", std::mem::size_of::<Content>());
+
+    println!("\n--- {} items, best of {} rounds ---", N, ROUNDS);
+    bench("impl Summary", || total_static(&news) + total_static(&tweets) + total_static(&wechats));
+    bench("&dyn Summary", || total_dyn_ref(&refs));
+    bench("Box<dyn Summary>", || total_boxed(&boxed));
+    bench("Content enum", || total_enum(&enums));
+}
@@ -0,0 +1,227 @@
+// Content: enum dispatch, the closed alternative to Box<dyn Summary>
+//
+// Vec<Box<dyn Summary>>           Vec<Content>
+// - open: any type, even ones     - closed: exactly these three types,
+//   registered at runtime           a new type means editing the enum
+// - one heap allocation per item  - items stored inline, no Box
+// - vtable call                   - match, which the compiler can inline
+// - element = fat pointer         - element = size of the largest variant
+//
+// C++ equivalent: std::variant<NewsArticle, Tweet, Wechat> + std::visit.
+//
+// Content implements Summary itself, so it works anywhere `impl Summary` or
+// `&dyn Summary` is accepted, and converts to Box<dyn Summary> when it has to
+// join an open collection.
+
+use crate::{NewsArticle, Summary, Tweet, Wechat};
+
+pub enum Content {
+    News(NewsArticle),
+    Tweet(Tweet),
+    Wechat(Wechat),
+}
+
+impl Content {
+    /// The wrapped value as a trait object
+    pub fn as_summary(&self) -> &dyn Summary {
+        match self {
+            Content::News(a) => a,
+            Content::Tweet(t) => t,
+            Content::Wechat(w) => w,
+        }
+    }
+
+    /// Variant name, same as the registry's type names
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Content::News(_) => "news",
+            Content::Tweet(_) => "tweet",
+            Content::Wechat(_) => "wechat",
+        }
+    }
+}
+
+// Every method is forwarded, not just summarize(): otherwise the overrides
+// (e.g. NewsArticle::summary_with_limit) would be replaced by the defaults.
+impl Summary for Content {
+    fn summarize(&self) -> String {
+        match self {
+            Content::News(a) => a.summarize(),
+            Content::Tweet(t) => t.summarize(),
+            Content::Wechat(w) => w.summarize(),
+        }
+    }
+
+    fn summarize_author(&self) -> String {
+        match self {
+            Content::News(a) => a.summarize_author(),
+            Content::Tweet(t) => t.summarize_author(),
+            Content::Wechat(w) => w.summarize_author(),
+        }
+    }
+
+    fn summary_with_limit(&self, max_chars: usize) -> String {
+        match self {
+            Content::News(a) => a.summary_with_limit(max_chars),
+            Content::Tweet(t) => t.summary_with_limit(max_chars),
+            Content::Wechat(w) => w.summary_with_limit(max_chars),
+        }
+    }
+
+    fn tags(&self) -> Vec<String> {
+        match self {
+            Content::News(a) => a.tags(),
+            Content::Tweet(t) => t.tags(),
+            Content::Wechat(w) => w.tags(),
+        }
+    }
+}
+
+impl From<NewsArticle> for Content {
+    fn from(article: NewsArticle) -> Content {
+        Content::News(article)
+    }
+}
+
+impl From<Tweet> for Content {
+    fn from(tweet: Tweet) -> Content {
+        Content::Tweet(tweet)
+    }
+}
+
+impl From<Wechat> for Content {
+    fn from(wechat: Wechat) -> Content {
+        Content::Wechat(wechat)
+    }
+}
+
+// From a box whose concrete type is known: move the value out, drop the allocation
+impl From<Box<NewsArticle>> for Content {
+    fn from(article: Box<NewsArticle>) -> Content {
+        Content::News(*article)
+    }
+}
+
+impl From<Box<Tweet>> for Content {
+    fn from(tweet: Box<Tweet>) -> Content {
+        Content::Tweet(*tweet)
+    }
+}
+
+impl From<Box<Wechat>> for Content {
+    fn from(wechat: Box<Wechat>) -> Content {
+        Content::Wechat(*wechat)
+    }
+}
+
+// Boxes the inner value, not the enum: the trait object's concrete type is
+// NewsArticle / Tweet / Wechat, exactly as if it had been boxed directly.
+impl From<Content> for Box<dyn Summary> {
+    fn from(content: Content) -> Box<dyn Summary> {
+        match content {
+            Content::News(a) => Box::new(a),
+            Content::Tweet(t) => Box::new(t),
+            Content::Wechat(w) => Box::new(w),
+        }
+    }
+}
+
+// The other direction only works at runtime: try each concrete type in turn.
+// A Content that was boxed as a whole is unwrapped too. Anything else
+// (e.g. a type added through the Registry) is handed back unchanged.
+impl TryFrom<Box<dyn Summary>> for Content {
+    type Error = Box<dyn Summary>;
+
+    fn try_from(item: Box<dyn Summary>) -> Result<Content, Box<dyn Summary>> {
+        let item = match item.downcast::<NewsArticle>() {
+            Ok(article) => return Ok(Content::from(article)),
+            Err(item) => item,
+        };
+        let item = match item.downcast::<Tweet>() {
+            Ok(tweet) => return Ok(Content::from(tweet)),
+            Err(item) => item,
+        };
+        let item = match item.downcast::<Wechat>() {
+            Ok(wechat) => return Ok(Content::from(wechat)),
+            Err(item) => item,
+        };
+        item.downcast::<Content>().map(|content| *content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+    use std::mem::size_of;
+
+    fn items() -> Vec<Content> {
+        vec![
+            fixtures::article().into(),
+            Tweet {
+                reply: true,
+                ..fixtures::tweet()
+            }
+            .into(),
+            Content::from(Box::new(fixtures::wechat())),
+        ]
+    }
+
+    #[test]
+    fn test_enum_dispatch() {
+        let items = items();
+        let kinds: Vec<&str> = items.iter().map(Content::kind).collect();
+        assert_eq!(kinds, vec!["news", "tweet", "wechat"]);
+
+        // Same results as through the concrete types
+        assert_eq!(items[0].summary_with_limit(42), "Penguins win the Stanley Cup Championship!");
+        assert_eq!(items[1].summarize(), "horse_ebooks replied: of course, as you probably already know, people");
+        assert_eq!(items[1].tags(), vec!["tweet", "reply"]);
+        assert_eq!(items[2].summarize_author(), "RustLang");
+
+        // Usable as impl Summary and as &dyn Summary
+        assert_eq!(crate::notify_static(&items[2]), "Breaking news! Wechat from RustLang: New release 1.80");
+        assert_eq!(crate::notify_dynamic(items[1].as_summary()), "Breaking news! horse_ebooks replied: of course, as you probably already know, people");
+    }
+
+    #[test]
+    fn test_closed_vs_open_collection() {
+        let closed = items();
+        let expected: Vec<String> = closed.iter().map(|c| c.summarize()).collect();
+
+        // Vec<Content>: elements inline, the size of the largest variant (+ at most a tag word)
+        assert!(size_of::<Content>() >= size_of::<NewsArticle>());
+        assert!(size_of::<Content>() <= size_of::<NewsArticle>() + size_of::<usize>());
+
+        // Vec<Box<dyn Summary>>: one fat pointer per element, values on the heap
+        let open: Vec<Box<dyn Summary>> = closed.into_iter().map(Box::from).collect();
+        assert_eq!(size_of::<Box<dyn Summary>>(), 2 * size_of::<usize>());
+        let summaries: Vec<String> = open.iter().map(|i| i.summarize()).collect();
+        assert_eq!(summaries, expected);
+        // The inner value was boxed, not the enum
+        assert_eq!(std::mem::size_of_val(&*open[1]), size_of::<Tweet>());
+
+        // ...and back again
+        let closed: Vec<Content> = open.into_iter().map(|i| Content::try_from(i).ok().unwrap()).collect();
+        let kinds: Vec<&str> = closed.iter().map(Content::kind).collect();
+        assert_eq!(kinds, vec!["news", "tweet", "wechat"]);
+    }
+
+    #[test]
+    fn test_try_from_dyn() {
+        struct Podcast;
+
+        impl Summary for Podcast {
+            fn summarize(&self) -> String {
+                String::from("Rustacean Station")
+            }
+        }
+
+        let boxed_enum: Box<dyn Summary> = Box::new(Content::from(fixtures::wechat()));
+        assert_eq!(Content::try_from(boxed_enum).ok().unwrap().kind(), "wechat");
+
+        // Not one of ours: the box comes back untouched
+        let podcast = Content::try_from(Box::new(Podcast) as Box<dyn Summary>).err().unwrap();
+        assert!(podcast.is::<Podcast>());
+    }
+}
@@ -0,0 +1,110 @@
+// Dispatch inspection: what static vs dynamic dispatch costs in memory
+//
+// The comments on notify_static / notify_dynamic make claims like "fat pointer"
+// and "vtable". This module turns them into numbers:
+//
+// - &NewsArticle / Box<NewsArticle> are thin pointers: 1 word.
+// - &dyn Summary / Box<dyn Summary> are fat pointers: data ptr + vtable ptr = 2 words.
+// - Option<Box<dyn Summary>> is still 2 words (null pointer optimization).
+// - Behind a dyn pointer, the concrete size is only known at runtime:
+//   size_of_val reads it from the vtable.
+//
+// The time side lives in benches/dispatch.rs (cargo bench -p topic05_traits_dyn_drop).
+
+use std::fmt::Write;
+use std::mem::{align_of, align_of_val, size_of, size_of_val};
+
+use crate::{NewsArticle, Summary, Tweet, Wechat};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeInfo {
+    pub name: String,
+    pub size: usize,
+    pub align: usize,
+}
+
+impl SizeInfo {
+    fn of<T>(name: &str) -> SizeInfo {
+        SizeInfo {
+            name: String::from(name),
+            size: size_of::<T>(),
+            align: align_of::<T>(),
+        }
+    }
+}
+
+/// Sizes of the concrete types and of the pointers used to reach them
+pub fn pointer_sizes() -> Vec<SizeInfo> {
+    vec![
+        SizeInfo::of::<NewsArticle>("NewsArticle"),
+        SizeInfo::of::<Tweet>("Tweet"),
+        SizeInfo::of::<Wechat>("Wechat"),
+        SizeInfo::of::<&NewsArticle>("&NewsArticle"),
+        SizeInfo::of::<Box<NewsArticle>>("Box<NewsArticle>"),
+        SizeInfo::of::<&dyn Summary>("&dyn Summary"),
+        SizeInfo::of::<Box<dyn Summary>>("Box<dyn Summary>"),
+        SizeInfo::of::<Option<Box<dyn Summary>>>("Option<Box<dyn Summary>>"),
+    ]
+}
+
+/// Size and alignment of the value behind a dyn pointer, read from its vtable
+pub fn object_size(item: &dyn Summary) -> (usize, usize) {
+    (size_of_val(item), align_of_val(item))
+}
+
+/// Memory used by a Vec<Box<dyn Summary>>: the Vec's own buffer of fat pointers
+/// plus one heap object per item. Heap data owned by the items (their Strings) is not counted.
+pub fn boxed_collection_bytes(items: &[Box<dyn Summary>]) -> usize {
+    let pointers = size_of_val(items);
+    let objects: usize = items.iter().map(|i| size_of_val(&**i)).sum();
+    pointers + objects
+}
+
+/// A printable table of pointer_sizes() and the dyn objects in `items`
+pub fn report(items: &[Box<dyn Summary>]) -> String {
+    let mut out = String::new();
+    writeln!(out, "{:<28} {:>6} {:>6}", "type", "size", "align").unwrap();
+    for info in pointer_sizes() {
+        writeln!(out, "{:<28} {:>6} {:>6}", info.name, info.size, info.align).unwrap();
+    }
+    for item in items {
+        let (size, align) = object_size(&**item);
+        let name = format!("dyn -> {}", item.summarize_author());
+        writeln!(out, "{:<28} {:>6} {:>6}", name, size, align).unwrap();
+    }
+    let bytes = boxed_collection_bytes(items);
+    writeln!(out, "{} boxed items use {} bytes (without their Strings)", items.len(), bytes).unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn size(name: &str) -> usize {
+        pointer_sizes().into_iter().find(|i| i.name == name).unwrap().size
+    }
+
+    #[test]
+    fn test_fat_pointers() {
+        let word = size_of::<usize>();
+        assert_eq!(size("&NewsArticle"), word);
+        assert_eq!(size("Box<NewsArticle>"), word);
+        assert_eq!(size("&dyn Summary"), 2 * word);
+        assert_eq!(size("Box<dyn Summary>"), 2 * word);
+        assert_eq!(size("Option<Box<dyn Summary>>"), 2 * word);
+    }
+
+    #[test]
+    fn test_object_size_from_vtable() {
+        let items: Vec<Box<dyn Summary>> = vec![Box::new(fixtures::tweet()), Box::new(fixtures::wechat())];
+        assert_eq!(object_size(&*items[0]).0, size_of::<Tweet>());
+        assert_eq!(object_size(&*items[1]).0, size_of::<Wechat>());
+        assert_eq!(
+            boxed_collection_bytes(&items),
+            2 * size_of::<Box<dyn Summary>>() + size_of::<Tweet>() + size_of::<Wechat>()
+        );
+        assert_eq!(report(&items).lines().count(), 1 + 8 + 2 + 1);
+    }
+}
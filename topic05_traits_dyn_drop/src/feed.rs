@@ -0,0 +1,237 @@
+// Feed: a stream of mixed content
+//
+// The Vec<Box<dyn Summary>> in the tests can only be iterated. Feed tags each item with
+// a timestamp and a source, then offers the usual feed operations: sort, filter by
+// source, dedup, paging and a digest.
+//
+// Every operation goes through Summary's vtable (summarize()), so Feed neither knows nor
+// cares whether an item is a NewsArticle, a Tweet or a Wechat. Adding a content type
+// needs no change here. Only callers need concrete types: items_of::<T> hands the items
+// to of_type in lib.rs to downcast them; Feed itself never looks at the type.
+
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use crate::{notify_dynamic, of_type, Summary};
+
+/// One item in the feed
+pub struct FeedItem {
+    /// Unix timestamp (seconds)
+    pub timestamp: u64,
+    /// Where it came from, e.g. "news", "twitter", "wechat"
+    pub source: String,
+    pub item: Box<dyn Summary>,
+}
+
+/// Direction for sorting by time
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Order {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+}
+
+#[derive(Default)]
+pub struct Feed {
+    items: Vec<FeedItem>,
+}
+
+impl Feed {
+    pub fn new() -> Feed {
+        Feed::default()
+    }
+
+    pub fn push(&mut self, timestamp: u64, source: &str, item: Box<dyn Summary>) {
+        self.items.push(FeedItem {
+            timestamp,
+            source: String::from(source),
+            item,
+        });
+    }
+
+    /// Same as push, but boxes the item for the caller
+    pub fn add(&mut self, timestamp: u64, source: &str, item: impl Summary + 'static) {
+        self.push(timestamp, source, Box::new(item));
+    }
+
+    pub fn items(&self) -> &[FeedItem] {
+        &self.items
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Sort by timestamp. The sort is stable: items with the same timestamp keep the order they were added in.
+    pub fn sort(&mut self, order: Order) {
+        match order {
+            Order::NewestFirst => self.items.sort_by_key(|i| std::cmp::Reverse(i.timestamp)),
+            Order::OldestFirst => self.items.sort_by_key(|i| i.timestamp),
+        }
+    }
+
+    /// All items from one source, in the current order
+    pub fn by_source<'f>(&'f self, source: &'f str) -> impl Iterator<Item = &'f FeedItem> + 'f {
+        self.items.iter().filter(move |i| i.source == source)
+    }
+
+    /// Keep only the items matching the predicate
+    pub fn retain(&mut self, f: impl FnMut(&FeedItem) -> bool) {
+        self.items.retain(f);
+    }
+
+    /// Remove items whose summarize() is a duplicate, keeping the first one, and return
+    /// how many were removed. Common when several sources carry the same story.
+    pub fn dedup(&mut self) -> usize {
+        let before = self.items.len();
+        let mut seen = HashSet::new();
+        self.items.retain(|i| seen.insert(i.item.summarize()));
+        before - self.items.len()
+    }
+
+    /// Page number `page` (0-based), `per_page` items per page
+    pub fn page(&self, page: usize, per_page: usize) -> Page<'_> {
+        let total_pages = if per_page == 0 {
+            0
+        } else {
+            self.items.len().div_ceil(per_page)
+        };
+        let start = page.saturating_mul(per_page).min(self.items.len());
+        let end = start.saturating_add(per_page).min(self.items.len());
+        Page {
+            items: &self.items[start..end],
+            page,
+            total_pages,
+        }
+    }
+
+    /// Digest of the whole feed
+    pub fn digest(&self) -> String {
+        digest(&self.items)
+    }
+}
+
+/// All items of type `T`, in the current order.
+/// Usage: `items_of::<Tweet>(&feed)`, after which fields like reply and retweet can be read.
+pub fn items_of<T: Summary>(feed: &Feed) -> impl Iterator<Item = &T> {
+    of_type(feed.items.iter().map(|i| &i.item))
+}
+
+/// One page of results, borrowing the Feed's items
+pub struct Page<'f> {
+    pub items: &'f [FeedItem],
+    pub page: usize,
+    pub total_pages: usize,
+}
+
+impl<'f> Page<'f> {
+    pub fn has_next(&self) -> bool {
+        self.page + 1 < self.total_pages
+    }
+
+    pub fn digest(&self) -> String {
+        digest(self.items)
+    }
+}
+
+// One line per item: [timestamp] (source) Breaking news! ...
+fn digest(items: &[FeedItem]) -> String {
+    let mut out = String::new();
+    for i in items {
+        writeln!(out, "[{}] ({}) {}", i.timestamp, i.source, notify_dynamic(&*i.item)).unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{sample_feed, tweet_saying, wechat};
+    use crate::{NewsArticle, Tweet, Wechat};
+
+    fn summaries(items: &[FeedItem]) -> Vec<String> {
+        items.iter().map(|i| i.item.summarize()).collect()
+    }
+
+    #[test]
+    fn test_items_of() {
+        let mut feed = sample_feed();
+        feed.sort(Order::OldestFirst);
+
+        let tweets: Vec<&str> = items_of::<Tweet>(&feed).map(|t| t.content.as_str()).collect();
+        assert_eq!(tweets, vec!["Rust 1.80 is out", "LazyCell is stable"]);
+        assert_eq!(items_of::<NewsArticle>(&feed).next().unwrap().author, "Iceburgh");
+        assert_eq!(items_of::<Wechat>(&feed).count(), 1);
+        assert_eq!(items_of::<crate::content::Content>(&feed).count(), 0);
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut feed = sample_feed();
+        feed.sort(Order::NewestFirst);
+        let times: Vec<u64> = feed.items().iter().map(|i| i.timestamp).collect();
+        assert_eq!(times, vec![300, 300, 200, 100]);
+        // Stable sort: same timestamp keeps insertion order
+        assert_eq!(feed.items()[0].item.summarize(), "horse_ebooks: Rust 1.80 is out");
+
+        feed.sort(Order::OldestFirst);
+        let sources: Vec<&str> = feed.items().iter().map(|i| i.source.as_str()).collect();
+        assert_eq!(sources, vec!["news", "wechat", "twitter", "twitter"]);
+    }
+
+    #[test]
+    fn test_filter_by_source() {
+        let mut feed = sample_feed();
+        assert_eq!(feed.by_source("twitter").count(), 2);
+        assert_eq!(feed.by_source("rss").count(), 0);
+
+        feed.retain(|i| i.source != "twitter");
+        assert_eq!(feed.len(), 2);
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut feed = sample_feed();
+        // The same tweet picked up by a second source
+        feed.add(400, "aggregator", tweet_saying("Rust 1.80 is out"));
+        feed.add(500, "wechat", wechat());
+        assert_eq!(feed.dedup(), 2);
+        assert_eq!(feed.len(), 4);
+        assert_eq!(feed.by_source("aggregator").count(), 0);
+        assert_eq!(feed.dedup(), 0);
+    }
+
+    #[test]
+    fn test_pagination() {
+        let feed = sample_feed();
+        let first = feed.page(0, 3);
+        assert_eq!(first.items.len(), 3);
+        assert_eq!(first.total_pages, 2);
+        assert!(first.has_next());
+
+        let last = feed.page(1, 3);
+        assert_eq!(summaries(last.items), vec!["horse_ebooks: LazyCell is stable"]);
+        assert!(!last.has_next());
+
+        assert!(feed.page(5, 3).items.is_empty());
+        assert_eq!(feed.page(0, 0).total_pages, 0);
+        assert_eq!(Feed::new().page(0, 10).total_pages, 0);
+    }
+
+    #[test]
+    fn test_digest() {
+        let mut feed = sample_feed();
+        feed.sort(Order::NewestFirst);
+        assert_eq!(
+            feed.page(1, 2).digest(),
+            "[200] (wechat) Breaking news! Wechat from RustLang: New release 1.80\n\
+             [100] (news) Breaking news! Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)\n"
+        );
+        assert_eq!(feed.digest().lines().count(), 4);
+        assert_eq!(Feed::new().digest(), "");
+    }
+}
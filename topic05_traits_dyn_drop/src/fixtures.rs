@@ -0,0 +1,52 @@
+// Shared test data
+//
+// One factory per content type, so every test module starts from the same values.
+// Tests that need a variation override just the fields they care about:
+//
+// let reply = Tweet { reply: true, ..fixtures::tweet() };
+
+use crate::feed::Feed;
+use crate::{NewsArticle, Tweet, Wechat};
+
+pub fn article() -> NewsArticle {
+    NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup Championship!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
+    }
+}
+
+pub fn tweet() -> Tweet {
+    Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+    }
+}
+
+pub fn wechat() -> Wechat {
+    Wechat {
+        official_account: String::from("RustLang"),
+        title: String::from("New release 1.80"),
+    }
+}
+
+/// A tweet from the default account with different text
+pub fn tweet_saying(content: &str) -> Tweet {
+    Tweet {
+        content: String::from(content),
+        ..tweet()
+    }
+}
+
+/// Four items from three sources, not in time order; two share timestamp 300.
+pub fn sample_feed() -> Feed {
+    let mut feed = Feed::new();
+    feed.add(300, "twitter", tweet_saying("Rust 1.80 is out"));
+    feed.add(100, "news", article());
+    feed.add(200, "wechat", wechat());
+    feed.add(300, "twitter", tweet_saying("LazyCell is stable"));
+    feed
+}
@@ -23,8 +23,51 @@ pub struct Wechat {
     pub title: String,
 }
 
-pub trait Summary {
+use std::any::Any;
+
+// Downcasting: getting the concrete type back from a &dyn Summary.
+//
+// dyn Summary only exposes Summary's methods. To read Tweet::reply we need
+// std::any::Any (like dynamic_cast<Tweet*> in C++, backed by a TypeId instead of RTTI).
+// Any is a supertrait of Summary, so a &dyn Summary can be upcast to &dyn Any
+// (trait upcasting, Rust 1.86+); the downcast methods on `dyn Summary` below do that.
+//
+// Cost: Summary implementors must be 'static (no borrowed fields), because Any is.
+
+pub trait Summary: Any {
     fn summarize(&self) -> String;
+
+    // Default methods: like non-pure virtual functions in C++.
+    // Implementors get them for free and may override them.
+
+    /// Who wrote it. Types that know their author should override this.
+    fn summarize_author(&self) -> String {
+        String::from("Anonymous")
+    }
+
+    /// `summarize()` cut to at most `max_chars` characters (not bytes), ending with "…" if cut.
+    fn summary_with_limit(&self, max_chars: usize) -> String {
+        truncate_chars(&self.summarize(), max_chars)
+    }
+
+    /// Labels for filtering and routing. No tags by default.
+    fn tags(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+/// Truncate to at most `max_chars` chars; the "…" counts towards the limit.
+/// Cuts on char boundaries, so multi-byte text like "你好" is never split in half.
+pub fn truncate_chars(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        return String::from(s);
+    }
+    if max_chars == 0 {
+        return String::new();
+    }
+    let mut out: String = s.chars().take(max_chars - 1).collect();
+    out.push('…');
+    out
 }
 
 // Placeholder implementations so it compiles (but fails tests if logic is wrong)
@@ -33,19 +76,111 @@ impl Summary for NewsArticle {
         format!("{}, by {} ({})", self.headline, self.author, self.location)
         // String::from("Implement me")
     }
+
+    fn summarize_author(&self) -> String {
+        self.author.clone()
+    }
+
+    // Override: when the full summary doesn't fit, the headline alone is more useful
+    // than "Penguins win the Stanley Cup Championship!, by Ice…".
+    fn summary_with_limit(&self, max_chars: usize) -> String {
+        let full = self.summarize();
+        if full.chars().count() <= max_chars {
+            full
+        } else {
+            truncate_chars(&self.headline, max_chars)
+        }
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![String::from("news"), self.location.clone()]
+    }
 }
 
 impl Summary for Tweet {
     fn summarize(&self) -> String {
-        format!("{}: {}", self.username, self.content)
+        if self.retweet {
+            format!("{} retweeted: {}", self.username, self.content)
+        } else if self.reply {
+            format!("{} replied: {}", self.username, self.content)
+        } else {
+            format!("{}: {}", self.username, self.content)
+        }
         // String::from("Implement me")
     }
+
+    fn summarize_author(&self) -> String {
+        format!("@{}", self.username)
+    }
+
+    fn tags(&self) -> Vec<String> {
+        let mut tags = vec![String::from("tweet")];
+        if self.reply {
+            tags.push(String::from("reply"));
+        }
+        if self.retweet {
+            tags.push(String::from("retweet"));
+        }
+        tags
+    }
 }
 
 impl Summary for Wechat {
     fn summarize(&self) -> String {
         format!("Wechat from {}: {}", self.official_account, self.title)
     }
+
+    fn summarize_author(&self) -> String {
+        self.official_account.clone()
+    }
+
+    fn tags(&self) -> Vec<String> {
+        vec![String::from("wechat")]
+    }
+}
+
+// Inherent methods, not trait methods: they exist only on the trait object, so for
+// `b: Box<dyn Summary>` the call `b.as_any()` always derefs to the dyn Summary and
+// sees the concrete type inside, never the Box. (A default method on Summary could
+// not do this: coercing `&Self` to `&dyn Any` in its body needs `Self: Sized`.)
+impl dyn Summary {
+    pub fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    pub fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    pub fn is<T: Summary>(&self) -> bool {
+        self.as_any().is::<T>()
+    }
+
+    pub fn downcast_ref<T: Summary>(&self) -> Option<&T> {
+        self.as_any().downcast_ref::<T>()
+    }
+
+    pub fn downcast_mut<T: Summary>(&mut self) -> Option<&mut T> {
+        self.as_any_mut().downcast_mut::<T>()
+    }
+
+    /// Take the concrete value back out of the box; gives the box back on a type mismatch.
+    pub fn downcast<T: Summary>(self: Box<Self>) -> Result<Box<T>, Box<dyn Summary>> {
+        if self.is::<T>() {
+            let any: Box<dyn Any> = self;
+            Ok(any.downcast::<T>().unwrap())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+/// The items of type `T`, in order; everything else is skipped.
+/// Accepts `&Vec<Box<dyn Summary>>`, a slice, or any iterator of `&Box<dyn Summary>`.
+pub fn of_type<'a, T: Summary>(
+    items: impl IntoIterator<Item = &'a Box<dyn Summary>>,
+) -> impl Iterator<Item = &'a T> {
+    items.into_iter().filter_map(|i| i.downcast_ref::<T>())
 }
 
 // === Part 1: Functions showcasing Static vs Dynamic Dispatch ===
@@ -69,6 +204,16 @@ pub fn notify_dynamic(item: &dyn Summary) -> String {
     format!("Breaking news! {}", item.summarize())
 }
 
+pub mod feed;
+pub mod render;
+pub mod registry;
+pub mod record;
+pub mod dispatch;
+pub mod content;
+
+#[cfg(test)]
+mod fixtures;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +282,97 @@ mod tests {
         assert_eq!(results[1], "Breaking news! horse_ebooks: of course, as you probably already know, people");
         assert_eq!(results[2], "Breaking news! Wechat from RustLang: New release 1.80");
     }
+
+    // Only implements the required method: everything else comes from the defaults.
+    struct Plain(&'static str);
+
+    impl Summary for Plain {
+        fn summarize(&self) -> String {
+            String::from(self.0)
+        }
+    }
+
+    #[test]
+    fn test_default_methods() {
+        let plain = Plain("你好, world");
+        assert_eq!(plain.summarize_author(), "Anonymous");
+        assert!(plain.tags().is_empty());
+        // Counted in chars, cut on char boundaries
+        assert_eq!(plain.summary_with_limit(20), "你好, world");
+        assert_eq!(plain.summary_with_limit(9), "你好, world");
+        assert_eq!(plain.summary_with_limit(3), "你好…");
+        assert_eq!(plain.summary_with_limit(1), "…");
+        assert_eq!(plain.summary_with_limit(0), "");
+    }
+
+    #[test]
+    fn test_overrides() {
+        let (article, tweet, wechat) = get_test_data();
+
+        assert_eq!(article.summarize_author(), "Iceburgh");
+        assert_eq!(tweet.summarize_author(), "@horse_ebooks");
+        assert_eq!(wechat.summarize_author(), "RustLang");
+
+        assert_eq!(article.tags(), vec!["news", "Pittsburgh, PA, USA"]);
+        assert_eq!(tweet.tags(), vec!["tweet"]);
+        assert_eq!(wechat.tags(), vec!["wechat"]);
+
+        // NewsArticle overrides summary_with_limit: falls back to the headline
+        assert_eq!(article.summary_with_limit(100), article.summarize());
+        assert_eq!(article.summary_with_limit(42), "Penguins win the Stanley Cup Championship!");
+        assert_eq!(article.summary_with_limit(10), "Penguins …");
+        // Tweet and Wechat use the default, which calls their own summarize()
+        assert_eq!(tweet.summary_with_limit(15), "horse_ebooks: …");
+        assert_eq!(wechat.summary_with_limit(11), "Wechat fro…");
+    }
+
+    #[test]
+    fn test_tweet_flags() {
+        let (_, mut tweet, _) = get_test_data();
+        tweet.reply = true;
+        assert_eq!(tweet.summarize(), "horse_ebooks replied: of course, as you probably already know, people");
+        assert_eq!(tweet.tags(), vec!["tweet", "reply"]);
+
+        tweet.reply = false;
+        tweet.retweet = true;
+        assert_eq!(tweet.summarize(), "horse_ebooks retweeted: of course, as you probably already know, people");
+        assert_eq!(tweet.tags(), vec!["tweet", "retweet"]);
+    }
+
+    #[test]
+    fn test_downcast() {
+        let (article, tweet, wechat) = get_test_data();
+        let mut items: Vec<Box<dyn Summary>> = vec![Box::new(article), Box::new(tweet), Box::new(wechat)];
+
+        assert!(items[1].is::<Tweet>());
+        assert!(!items[1].is::<NewsArticle>());
+        // Concrete fields are reachable again
+        let tweet = items[1].downcast_ref::<Tweet>().unwrap();
+        assert!(!tweet.reply);
+        assert!(items[0].downcast_ref::<Tweet>().is_none());
+
+        items[1].downcast_mut::<Tweet>().unwrap().retweet = true;
+        assert_eq!(items[1].tags(), vec!["tweet", "retweet"]);
+
+        // as_any() on the Box sees the Tweet inside, not the Box
+        assert!(items[1].as_any().is::<Tweet>());
+
+        let headlines: Vec<&str> = of_type::<NewsArticle>(&items).map(|a| a.headline.as_str()).collect();
+        assert_eq!(headlines, vec!["Penguins win the Stanley Cup Championship!"]);
+        assert_eq!(of_type::<Plain>(&items).count(), 0);
+
+        let wechat = items.pop().unwrap();
+        let wechat = wechat.downcast::<Tweet>().err().unwrap();
+        let wechat: Box<Wechat> = wechat.downcast::<Wechat>().ok().unwrap();
+        assert_eq!(wechat.title, "New release 1.80");
+    }
+
+    #[test]
+    fn test_default_methods_through_dyn() {
+        // Default methods live in the vtable too: overrides are picked at runtime
+        let (article, tweet, _) = get_test_data();
+        let items: Vec<Box<dyn Summary>> = vec![Box::new(article), Box::new(tweet), Box::new(Plain("x"))];
+        let authors: Vec<String> = items.iter().map(|i| i.summarize_author()).collect();
+        assert_eq!(authors, vec!["Iceburgh", "@horse_ebooks", "Anonymous"]);
+    }
 }
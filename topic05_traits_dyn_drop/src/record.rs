@@ -0,0 +1,347 @@
+// Record format: load and store content as text
+//
+// One field per line, records separated by blank lines, `#` starts a comment line:
+//
+// # exported feed
+// type: tweet
+// username: horse_ebooks
+// content: first line\nsecond line
+// reply: false
+// retweet: false
+//
+// type: wechat
+// official_account: RustLang
+// title: New release 1.80
+//
+// Values are escaped so every field stays on one line: `\` -> `\\`, newline -> `\n`,
+// carriage return -> `\r`. Everything after "key: " is the value, including
+// leading/trailing spaces.
+//
+// Parsing goes through the Registry: `type` picks the constructor, the other lines
+// become its Fields. So a type registered at runtime can be loaded too.
+// Writing goes through the ToRecord trait, implemented by each content type.
+
+use std::fmt;
+
+use crate::registry::{Fields, Registry, RegistryError};
+use crate::{NewsArticle, Summary, Tweet, Wechat};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordErrorKind {
+    /// A record without a `type:` line
+    MissingType,
+    /// A line that is not `key: value`
+    MalformedLine,
+    DuplicateField(String),
+    InvalidEscape(char),
+    /// Unknown type, missing required field, invalid value...
+    Registry(RegistryError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordError {
+    /// 1-based line number: the offending line, or the first line of the record
+    pub line: usize,
+    pub kind: RecordErrorKind,
+}
+
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            RecordErrorKind::MissingType => write!(f, "record has no 'type' field"),
+            RecordErrorKind::MalformedLine => write!(f, "expected 'key: value'"),
+            RecordErrorKind::DuplicateField(key) => write!(f, "duplicate field '{}'", key),
+            RecordErrorKind::InvalidEscape(c) => write!(f, "invalid escape '\\{}'", c),
+            RecordErrorKind::Registry(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for RecordError {}
+
+/// A record before it is turned into a value: its type, fields and first line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawRecord {
+    pub line: usize,
+    pub type_name: String,
+    pub fields: Fields,
+}
+
+/// Split text into records without building them
+pub fn parse_raw(text: &str) -> Result<Vec<RawRecord>, RecordError> {
+    let mut records = Vec::new();
+    // (first line, type, fields) of the record being read
+    let mut current: Option<(usize, Option<String>, Fields)> = None;
+
+    let mut finish = |current: Option<(usize, Option<String>, Fields)>| -> Result<(), RecordError> {
+        if let Some((line, type_name, fields)) = current {
+            let type_name = type_name.ok_or(RecordError {
+                line,
+                kind: RecordErrorKind::MissingType,
+            })?;
+            records.push(RawRecord { line, type_name, fields });
+        }
+        Ok(())
+    };
+
+    for (i, raw) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let err = |kind| RecordError { line: line_no, kind };
+        if raw.trim().is_empty() {
+            finish(current.take())?;
+            continue;
+        }
+        if raw.trim_start().starts_with('#') {
+            continue;
+        }
+
+        let (key, value) = raw.split_once(':').ok_or(err(RecordErrorKind::MalformedLine))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(err(RecordErrorKind::MalformedLine));
+        }
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        let value = unescape(value).map_err(|c| err(RecordErrorKind::InvalidEscape(c)))?;
+
+        let (_, type_name, fields) = current.get_or_insert_with(|| (line_no, None, Fields::new()));
+        let duplicate = if key == "type" {
+            type_name.replace(value).is_some()
+        } else if fields.contains(key) {
+            true
+        } else {
+            fields.insert(key, &value);
+            false
+        };
+        if duplicate {
+            return Err(err(RecordErrorKind::DuplicateField(String::from(key))));
+        }
+    }
+    finish(current.take())?;
+    Ok(records)
+}
+
+/// Parse every record and build it through the registry
+pub fn parse_records(text: &str, registry: &Registry) -> Result<Vec<Box<dyn Summary>>, RecordError> {
+    parse_raw(text)?
+        .into_iter()
+        .map(|r| {
+            registry.build(&r.type_name, &r.fields).map_err(|e| RecordError {
+                line: r.line,
+                kind: RecordErrorKind::Registry(e),
+            })
+        })
+        .collect()
+}
+
+/// Types that can be written in the record format
+pub trait ToRecord {
+    /// The name the type is registered under
+    fn type_name(&self) -> &'static str;
+
+    /// Fields in the order they are written
+    fn fields(&self) -> Vec<(&'static str, String)>;
+
+    fn to_record(&self) -> String {
+        let mut out = format!("type: {}\n", self.type_name());
+        for (key, value) in self.fields() {
+            out.push_str(key);
+            out.push_str(": ");
+            out.push_str(&escape(&value));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl ToRecord for NewsArticle {
+    fn type_name(&self) -> &'static str {
+        "news"
+    }
+
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("headline", self.headline.clone()),
+            ("location", self.location.clone()),
+            ("author", self.author.clone()),
+            ("content", self.content.clone()),
+        ]
+    }
+}
+
+impl ToRecord for Tweet {
+    fn type_name(&self) -> &'static str {
+        "tweet"
+    }
+
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("username", self.username.clone()),
+            ("content", self.content.clone()),
+            ("reply", self.reply.to_string()),
+            ("retweet", self.retweet.to_string()),
+        ]
+    }
+}
+
+impl ToRecord for Wechat {
+    fn type_name(&self) -> &'static str {
+        "wechat"
+    }
+
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("official_account", self.official_account.clone()),
+            ("title", self.title.clone()),
+        ]
+    }
+}
+
+/// Write several records, separated by blank lines
+pub fn write_records(items: &[&dyn ToRecord]) -> String {
+    items
+        .iter()
+        .map(|item| item.to_record())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// The reverse of `escape`; returns the bad character after `\` on error.
+/// A lone `\` at the end of the value is an error too (reported as '\\').
+pub fn unescape(value: &str) -> Result<String, char> {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some(other) => return Err(other),
+            None => return Err('\\'),
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    // A value that needs escaping: newline and backslash
+    fn multiline_tweet() -> Tweet {
+        Tweet {
+            content: String::from("first line\nsecond \\ line"),
+            reply: true,
+            ..fixtures::tweet()
+        }
+    }
+
+    #[test]
+    fn test_write() {
+        assert_eq!(
+            multiline_tweet().to_record(),
+            "type: tweet\n\
+             username: horse_ebooks\n\
+             content: first line\\nsecond \\\\ line\n\
+             reply: true\n\
+             retweet: false\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let article = NewsArticle {
+            content: String::from("  Multi\r\nline: with colon  "),
+            ..fixtures::article()
+        };
+        let wechat = Wechat {
+            title: String::from("新版本 1.80"),
+            ..fixtures::wechat()
+        };
+        let text = write_records(&[&article, &multiline_tweet(), &wechat]);
+
+        let raw = parse_raw(&text).unwrap();
+        assert_eq!(raw.len(), 3);
+        assert_eq!(raw[0].fields.get("content"), Some("  Multi\r\nline: with colon  "));
+        assert_eq!(raw[1].line, 7);
+
+        let items = parse_records(&text, &Registry::with_builtin()).unwrap();
+        let summaries: Vec<String> = items.iter().map(|i| i.summarize()).collect();
+        assert_eq!(
+            summaries,
+            vec![article.summarize(), multiline_tweet().summarize(), wechat.summarize()]
+        );
+    }
+
+    #[test]
+    fn test_comments_and_blank_lines() {
+        let text = "# header\n\n\ntype: wechat\n# inline comment line\nofficial_account: A\ntitle: T\n\n\n";
+        let items = parse_records(text, &Registry::with_builtin()).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].summarize(), "Wechat from A: T");
+        assert!(parse_records("", &Registry::with_builtin()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        let registry = Registry::with_builtin();
+        let err = |text: &str| parse_records(text, &registry).err().unwrap();
+
+        assert_eq!(
+            err("type: tweet\nusername: u\n"),
+            RecordError {
+                line: 1,
+                kind: RecordErrorKind::Registry(RegistryError::MissingField {
+                    type_name: String::from("tweet"),
+                    field: String::from("content"),
+                }),
+            }
+        );
+        assert_eq!(
+            err("type: wechat\nofficial_account: A\ntitle: T\n\nusername: u\n").to_string(),
+            "line 5: record has no 'type' field"
+        );
+        assert_eq!(err("type: tweet\njust text\n").kind, RecordErrorKind::MalformedLine);
+        assert_eq!(err("type: tweet\n: value\n").line, 2);
+        assert_eq!(
+            err("type: tweet\ntype: news\n").kind,
+            RecordErrorKind::DuplicateField(String::from("type"))
+        );
+        assert_eq!(
+            err("type: tweet\nusername: a\nusername: b\n").kind,
+            RecordErrorKind::DuplicateField(String::from("username"))
+        );
+        assert_eq!(err("type: tweet\ncontent: bad \\t escape\n").kind, RecordErrorKind::InvalidEscape('t'));
+        assert_eq!(
+            err("type: rss\nurl: x\n").to_string(),
+            "line 1: unknown type 'rss'"
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        for s in ["", "plain", "a\nb", "back\\slash\\n", "\r\n\r\n", "尾部\\"] {
+            assert_eq!(unescape(&escape(s)).as_deref(), Ok(s));
+            assert!(!escape(s).contains('\n'));
+        }
+        assert_eq!(unescape("dangling\\"), Err('\\'));
+    }
+}
@@ -0,0 +1,288 @@
+// Registry: construct Summary types by name
+//
+// Without a registry, every place that loads content needs a match over all types:
+//
+// match type_name {
+//     "news" => Box::new(NewsArticle { ... }),
+//     "tweet" => Box::new(Tweet { ... }),
+//     ...                      // every new type means editing this match
+// }
+//
+// The registry turns this into data: a map from type name to (required fields, constructor).
+// Adding a type is one `register` call; loaders only ever see `Box<dyn Summary>`.
+//
+// Constructors are stored as Box<dyn Fn>, so both plain functions and closures
+// (e.g. capturing a default account name) can be registered.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::{NewsArticle, Summary, Tweet, Wechat};
+
+/// Generic key/value input for constructors (from a config file, a form, ...).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fields {
+    map: BTreeMap<String, String>,
+}
+
+impl Fields {
+    pub fn new() -> Fields {
+        Fields::default()
+    }
+
+    pub fn insert(&mut self, key: &str, value: &str) {
+        self.map.insert(String::from(key), String::from(value));
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.map.get(key).map(String::as_str)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// Owned copy of a field; empty if missing (use required fields for must-haves).
+    pub fn string(&self, key: &str) -> String {
+        self.get(key).map(String::from).unwrap_or_default()
+    }
+
+    /// "true"/"false" (also "yes"/"no", "1"/"0"); missing means false.
+    pub fn bool(&self, key: &str) -> Result<bool, RegistryError> {
+        match self.get(key) {
+            None => Ok(false),
+            Some("true" | "yes" | "1") => Ok(true),
+            Some("false" | "no" | "0") => Ok(false),
+            Some(other) => Err(RegistryError::InvalidField {
+                field: String::from(key),
+                value: String::from(other),
+            }),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.map.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+impl<'k, 'v> FromIterator<(&'k str, &'v str)> for Fields {
+    fn from_iter<I: IntoIterator<Item = (&'k str, &'v str)>>(iter: I) -> Fields {
+        let mut fields = Fields::new();
+        for (k, v) in iter {
+            fields.insert(k, v);
+        }
+        fields
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistryError {
+    UnknownType(String),
+    DuplicateType(String),
+    MissingField { type_name: String, field: String },
+    InvalidField { field: String, value: String },
+}
+
+impl fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryError::UnknownType(name) => write!(f, "unknown type '{}'", name),
+            RegistryError::DuplicateType(name) => write!(f, "type '{}' is already registered", name),
+            RegistryError::MissingField { type_name, field } => {
+                write!(f, "type '{}' requires field '{}'", type_name, field)
+            }
+            RegistryError::InvalidField { field, value } => {
+                write!(f, "invalid value '{}' for field '{}'", value, field)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegistryError {}
+
+pub type Constructor = Box<dyn Fn(&Fields) -> Result<Box<dyn Summary>, RegistryError>>;
+
+struct Entry {
+    required: Vec<String>,
+    constructor: Constructor,
+}
+
+#[derive(Default)]
+pub struct Registry {
+    types: HashMap<String, Entry>,
+}
+
+impl Registry {
+    /// An empty registry
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    /// A registry with "news", "tweet" and "wechat"
+    pub fn with_builtin() -> Registry {
+        let mut registry = Registry::new();
+        registry
+            .register("news", &["headline", "author", "location"], |f| {
+                Ok(Box::new(NewsArticle {
+                    headline: f.string("headline"),
+                    location: f.string("location"),
+                    author: f.string("author"),
+                    content: f.string("content"),
+                }))
+            })
+            .unwrap();
+        registry
+            .register("tweet", &["username", "content"], |f| {
+                Ok(Box::new(Tweet {
+                    username: f.string("username"),
+                    content: f.string("content"),
+                    reply: f.bool("reply")?,
+                    retweet: f.bool("retweet")?,
+                }))
+            })
+            .unwrap();
+        registry
+            .register("wechat", &["official_account", "title"], |f| {
+                Ok(Box::new(Wechat {
+                    official_account: f.string("official_account"),
+                    title: f.string("title"),
+                }))
+            })
+            .unwrap();
+        registry
+    }
+
+    /// Register a constructor. `required` fields are checked before it is called.
+    pub fn register<F>(&mut self, name: &str, required: &[&str], constructor: F) -> Result<(), RegistryError>
+    where
+        F: Fn(&Fields) -> Result<Box<dyn Summary>, RegistryError> + 'static,
+    {
+        if self.types.contains_key(name) {
+            return Err(RegistryError::DuplicateType(String::from(name)));
+        }
+        self.types.insert(
+            String::from(name),
+            Entry {
+                required: required.iter().map(|s| String::from(*s)).collect(),
+                constructor: Box::new(constructor),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.types.contains_key(name)
+    }
+
+    /// Registered type names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.types.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    pub fn required_fields(&self, name: &str) -> Option<&[String]> {
+        self.types.get(name).map(|e| e.required.as_slice())
+    }
+
+    /// Check required fields, then call the constructor
+    pub fn build(&self, name: &str, fields: &Fields) -> Result<Box<dyn Summary>, RegistryError> {
+        let entry = self
+            .types
+            .get(name)
+            .ok_or_else(|| RegistryError::UnknownType(String::from(name)))?;
+        if let Some(missing) = entry.required.iter().find(|f| !fields.contains(f)) {
+            return Err(RegistryError::MissingField {
+                type_name: String::from(name),
+                field: missing.clone(),
+            });
+        }
+        (entry.constructor)(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_types() {
+        let registry = Registry::with_builtin();
+        assert_eq!(registry.names(), vec!["news", "tweet", "wechat"]);
+
+        let tweet = registry
+            .build("tweet", &Fields::from_iter([("username", "rustlang"), ("content", "1.80!"), ("retweet", "yes")]))
+            .unwrap();
+        assert_eq!(tweet.summarize(), "rustlang retweeted: 1.80!");
+        assert_eq!(tweet.tags(), vec!["tweet", "retweet"]);
+
+        let news = registry
+            .build("news", &Fields::from_iter([("headline", "H"), ("author", "A"), ("location", "L")]))
+            .unwrap();
+        assert_eq!(news.summarize(), "H, by A (L)");
+
+        let wechat = registry
+            .build("wechat", &Fields::from_iter([("official_account", "RustLang"), ("title", "T")]))
+            .unwrap();
+        assert_eq!(wechat.summarize_author(), "RustLang");
+    }
+
+    #[test]
+    fn test_validation() {
+        let registry = Registry::with_builtin();
+        let err = registry
+            .build("tweet", &Fields::from_iter([("username", "rustlang")]))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            RegistryError::MissingField {
+                type_name: String::from("tweet"),
+                field: String::from("content"),
+            }
+        );
+        assert_eq!(err.to_string(), "type 'tweet' requires field 'content'");
+
+        let err = registry
+            .build("tweet", &Fields::from_iter([("username", "u"), ("content", "c"), ("reply", "maybe")]))
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "invalid value 'maybe' for field 'reply'");
+
+        let err = registry.build("rss", &Fields::new()).err().unwrap();
+        assert_eq!(err, RegistryError::UnknownType(String::from("rss")));
+    }
+
+    #[test]
+    fn test_register_new_type() {
+        // A new content type, added without touching any dispatch code
+        struct Podcast {
+            show: String,
+            episode: String,
+        }
+
+        impl Summary for Podcast {
+            fn summarize(&self) -> String {
+                format!("{} - {}", self.show, self.episode)
+            }
+        }
+
+        let mut registry = Registry::with_builtin();
+        let default_show = String::from("Rustacean Station");
+        registry
+            .register("podcast", &["episode"], move |f| {
+                Ok(Box::new(Podcast {
+                    show: f.get("show").map(String::from).unwrap_or_else(|| default_show.clone()),
+                    episode: f.string("episode"),
+                }))
+            })
+            .unwrap();
+
+        assert_eq!(registry.required_fields("podcast"), Some(&[String::from("episode")][..]));
+        let item = registry.build("podcast", &Fields::from_iter([("episode", "Ep. 1")])).unwrap();
+        assert_eq!(crate::notify_dynamic(&*item), "Breaking news! Rustacean Station - Ep. 1");
+
+        let dup = registry.register("tweet", &[], |_| unreachable!());
+        assert_eq!(dup, Err(RegistryError::DuplicateType(String::from("tweet"))));
+    }
+}
@@ -0,0 +1,339 @@
+// Renderer: pluggable output formats for Summary
+//
+// notify_static / notify_dynamic hardcode one format: "Breaking news! {}".
+// Different notification channels need different formats (a terminal wants plain text,
+// a chat bot wants Markdown, an email wants HTML, a webhook wants JSON).
+//
+// Two traits meet here:
+// - Summary: WHAT to say (summarize, summarize_author, tags).
+// - Renderer: HOW to say it.
+//
+// Both can be dispatched statically or dynamically, independently:
+// - render_static(&Html, &tweet)            -> one specialized copy per (Renderer, Summary) pair
+// - render_dynamic(&*renderer, &*item)      -> one copy, two vtable lookups
+//
+// Each renderer escapes everything that comes from the content, so a headline
+// like "<script>", a quote in a tweet or a summary starting with "# " can't add
+// markup or structure to the output. Markdown has no escape for a line break, so
+// escape_markdown turns line breaks into spaces (which is how a paragraph renders
+// them anyway).
+
+use crate::Summary;
+
+/// Everything a renderer needs, collected once from the Summary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub summary: String,
+    pub author: String,
+    pub tags: Vec<String>,
+}
+
+impl Message {
+    pub fn from_summary<S: Summary + ?Sized>(item: &S) -> Message {
+        Message {
+            summary: item.summarize(),
+            author: item.summarize_author(),
+            tags: item.tags(),
+        }
+    }
+}
+
+pub trait Renderer {
+    fn render(&self, msg: &Message) -> String;
+
+    /// MIME type of the output, for channels that need to label it.
+    fn content_type(&self) -> &'static str;
+}
+
+/// "Breaking news! ..." — the same text notify_static produces.
+pub struct PlainText;
+
+/// **Breaking news!** with author and tags; Markdown special characters are escaped.
+pub struct Markdown;
+
+/// An <article> fragment; HTML special characters are escaped.
+pub struct Html;
+
+/// A JSON object: {"summary": ..., "author": ..., "tags": [...]}
+pub struct Json;
+
+impl Renderer for PlainText {
+    fn render(&self, msg: &Message) -> String {
+        format!("Breaking news! {}", msg.summary)
+    }
+
+    fn content_type(&self) -> &'static str {
+        "text/plain"
+    }
+}
+
+impl Renderer for Markdown {
+    fn render(&self, msg: &Message) -> String {
+        let mut out = format!(
+            "**Breaking news!** {}\n\n_by {}_",
+            escape_markdown(&msg.summary),
+            escape_markdown(&msg.author)
+        );
+        if !msg.tags.is_empty() {
+            let tags: Vec<String> = msg.tags.iter().map(|t| code_span(t)).collect();
+            out.push_str("\n\nTags: ");
+            out.push_str(&tags.join(", "));
+        }
+        out
+    }
+
+    fn content_type(&self) -> &'static str {
+        "text/markdown"
+    }
+}
+
+impl Renderer for Html {
+    fn render(&self, msg: &Message) -> String {
+        let mut out = format!(
+            "<article><p><strong>Breaking news!</strong> {}</p><footer>by {}</footer>",
+            escape_html(&msg.summary),
+            escape_html(&msg.author)
+        );
+        if !msg.tags.is_empty() {
+            out.push_str("<ul class=\"tags\">");
+            for tag in &msg.tags {
+                out.push_str(&format!("<li>{}</li>", escape_html(tag)));
+            }
+            out.push_str("</ul>");
+        }
+        out.push_str("</article>");
+        out
+    }
+
+    fn content_type(&self) -> &'static str {
+        "text/html"
+    }
+}
+
+impl Renderer for Json {
+    fn render(&self, msg: &Message) -> String {
+        let tags: Vec<String> = msg.tags.iter().map(|t| json_string(t)).collect();
+        format!(
+            "{{\"summary\":{},\"author\":{},\"tags\":[{}]}}",
+            json_string(&msg.summary),
+            json_string(&msg.author),
+            tags.join(",")
+        )
+    }
+
+    fn content_type(&self) -> &'static str {
+        "application/json"
+    }
+}
+
+/// Static dispatch on both sides: specialized for this exact (Renderer, Summary) pair.
+pub fn render_static(renderer: &impl Renderer, item: &impl Summary) -> String {
+    renderer.render(&Message::from_summary(item))
+}
+
+/// Dynamic dispatch on both sides: pick the format and the content at runtime.
+pub fn render_dynamic(renderer: &dyn Renderer, item: &dyn Summary) -> String {
+    renderer.render(&Message::from_summary(item))
+}
+
+/// Look up a renderer by name ("plain", "markdown", "html", "json").
+pub fn renderer_for(name: &str) -> Option<Box<dyn Renderer>> {
+    match name {
+        "plain" => Some(Box::new(PlainText)),
+        "markdown" => Some(Box::new(Markdown)),
+        "html" => Some(Box::new(Html)),
+        "json" => Some(Box::new(Json)),
+        _ => None,
+    }
+}
+
+pub fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Escape text for use inside a Markdown paragraph.
+///
+/// Besides inline markup, anything that could start a new block is neutralized:
+/// line breaks become spaces, leading indentation is dropped (it would make a code
+/// block), and a leading `-`, `+`, `=` or `1.` / `1)` is escaped.
+pub fn escape_markdown(s: &str) -> String {
+    let s = s.trim_start_matches([' ', '\t']);
+    let digits = s.bytes().take_while(u8::is_ascii_digit).count();
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\r' | '\n' => {
+                // \r\n is one line break
+                if c == '\r' {
+                    chars.next_if(|&(_, next)| next == '\n');
+                }
+                out.push(' ');
+                continue;
+            }
+            '\\' | '*' | '_' | '`' | '[' | ']' | '#' | '<' | '>' | '|' => out.push('\\'),
+            '-' | '+' | '=' if i == 0 => out.push('\\'),
+            '.' | ')' if digits > 0 && i == digits => out.push('\\'),
+            _ => {}
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// `s` as a Markdown code span. The fence is one backtick longer than the longest
+/// run of backticks in `s`, so those stay literal; backslashes don't escape in code.
+fn code_span(s: &str) -> String {
+    let s = s.replace("\r\n", " ").replace(['\r', '\n'], " ");
+    let mut longest = 0;
+    let mut run = 0;
+    for c in s.chars() {
+        run = if c == '`' { run + 1 } else { 0 };
+        longest = longest.max(run);
+    }
+    let fence = "`".repeat(longest + 1);
+    // The parser strips one space from each end when both are there, so pad when the
+    // content touches a backtick or already has a space on both ends.
+    let pad = s.starts_with('`')
+        || s.ends_with('`')
+        || (s.starts_with(' ') && s.ends_with(' ') && !s.trim_matches(' ').is_empty());
+    if pad {
+        format!("{} {} {}", fence, s, fence)
+    } else {
+        format!("{}{}{}", fence, s, fence)
+    }
+}
+
+/// A quoted JSON string literal.
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, article, wechat};
+    use crate::{notify_dynamic, notify_static, Tweet};
+
+    // Characters that every format has to escape: _ < > & " *
+    fn escaping_tweet() -> Tweet {
+        Tweet {
+            username: String::from("rust_lang"),
+            content: String::from("Use <T> & \"quotes\" *carefully*"),
+            reply: true,
+            ..fixtures::tweet()
+        }
+    }
+
+    #[test]
+    fn test_plain_matches_notify() {
+        assert_eq!(render_static(&PlainText, &article()), notify_static(&article()));
+        assert_eq!(render_dynamic(&PlainText, &wechat()), notify_dynamic(&wechat()));
+    }
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(
+            render_static(&Markdown, &escaping_tweet()),
+            "**Breaking news!** rust\\_lang replied: Use \\<T\\> & \"quotes\" \\*carefully\\*\n\n\
+             _by @rust\\_lang_\n\nTags: `tweet`, `reply`"
+        );
+    }
+
+    #[test]
+    fn test_markdown_block_starts() {
+        // Nothing in the text can start a heading, list, quote, code block or new paragraph
+        assert_eq!(escape_markdown("# not a heading"), "\\# not a heading");
+        assert_eq!(escape_markdown("- one\n+ two\r\n\r\n1. three"), "\\- one + two  1. three");
+        assert_eq!(escape_markdown("1. first"), "1\\. first");
+        assert_eq!(escape_markdown("  2) second"), "2\\) second");
+        assert_eq!(escape_markdown("    indented code"), "indented code");
+        assert_eq!(escape_markdown("===\n> quote"), "\\=== \\> quote");
+        // Only a leading marker is a block start
+        assert_eq!(escape_markdown("a - b + c, 2. d"), "a - b + c, 2. d");
+        assert_eq!(escape_markdown("1999 was a year"), "1999 was a year");
+    }
+
+    #[test]
+    fn test_markdown_tags_with_backticks() {
+        struct Tagged(Vec<&'static str>);
+        impl Summary for Tagged {
+            fn summarize(&self) -> String {
+                String::from("tagged")
+            }
+            fn tags(&self) -> Vec<String> {
+                self.0.iter().map(|t| String::from(*t)).collect()
+            }
+        }
+
+        let item = Tagged(vec!["rust", "a`b", "``x", "c\\", " pad "]);
+        let rendered = render_static(&Markdown, &item);
+        let tags = rendered.split_once("Tags: ").unwrap().1;
+        // Backticks are kept, not replaced: the fence is longer than any run inside
+        assert_eq!(tags, "`rust`, ``a`b``, ``` ``x ```, `c\\`, `  pad  `");
+
+        assert_eq!(code_span("two\nlines"), "`two lines`");
+    }
+
+    #[test]
+    fn test_html_escaping() {
+        assert_eq!(
+            render_static(&Html, &escaping_tweet()),
+            "<article><p><strong>Breaking news!</strong> rust_lang replied: \
+             Use &lt;T&gt; &amp; &quot;quotes&quot; *carefully*</p>\
+             <footer>by @rust_lang</footer>\
+             <ul class=\"tags\"><li>tweet</li><li>reply</li></ul></article>"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            render_static(&Json, &wechat()),
+            "{\"summary\":\"Wechat from RustLang: New release 1.80\",\"author\":\"RustLang\",\"tags\":[\"wechat\"]}"
+        );
+        assert_eq!(json_string("a\"b\\c\nd\u{1}é"), "\"a\\\"b\\\\c\\nd\\u0001é\"");
+    }
+
+    #[test]
+    fn test_dynamic_entry_point() {
+        let items: Vec<Box<dyn Summary>> = vec![Box::new(escaping_tweet()), Box::new(wechat())];
+        let mut out = Vec::new();
+        for name in ["plain", "markdown", "html", "json"] {
+            let renderer = renderer_for(name).unwrap();
+            for item in &items {
+                out.push((renderer.content_type(), render_dynamic(&*renderer, &**item)));
+            }
+        }
+        assert_eq!(out.len(), 8);
+        assert_eq!(out[2].0, "text/markdown");
+        assert!(out[5].1.starts_with("<article>"));
+        assert_eq!(out[7].1, render_static(&Json, &wechat()));
+        assert!(renderer_for("xml").is_none());
+    }
+}
@@ -3,19 +3,54 @@
 // Goal: Create a `MockMessenger` that records messages sent to it.
 // Since `send` takes &self (immutable), we need RefCell to store the messages.
 
+use std::cell::{Cell, RefCell};
+use std::fmt;
+
 pub trait Messenger {
     fn send(&self, msg: &str);
+
+    /// Fallible version of `send`. Real backends (network, SMTP...) override this;
+    /// the default just calls `send` and never fails.
+    fn try_send(&self, msg: &str) -> Result<(), SendError> {
+        self.send(msg);
+        Ok(())
+    }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendError {
+    pub reason: String,
+}
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "send failed: {}", self.reason)
+    }
+}
+
+impl std::error::Error for SendError {}
+
+#[derive(Default)]
 pub struct MockMessenger {
-    pub sent_messages: std::cell::RefCell<Vec<String>>,
+    pub sent_messages: RefCell<Vec<String>>,
+    // Cell is enough for Copy counters: no borrow tracking needed
+    attempts: Cell<usize>,
+    failures_left: Cell<usize>,
 }
 
 impl MockMessenger {
     pub fn new() -> MockMessenger {
-        MockMessenger {
-            sent_messages: std::cell::RefCell::new(vec![]),
-        }
+        MockMessenger::default()
+    }
+
+    /// Make the next `n` calls to `try_send` fail (failure injection for tests).
+    pub fn fail_next(&self, n: usize) {
+        self.failures_left.set(n);
+    }
+
+    /// Number of `try_send` calls, including failed ones.
+    pub fn attempts(&self) -> usize {
+        self.attempts.get()
     }
 }
 
@@ -23,8 +58,23 @@ impl Messenger for MockMessenger {
     fn send(&self, msg: &str) {
         self.sent_messages.borrow_mut().push(String::from(msg));
     }
+
+    fn try_send(&self, msg: &str) -> Result<(), SendError> {
+        self.attempts.set(self.attempts.get() + 1);
+        let left = self.failures_left.get();
+        if left > 0 {
+            self.failures_left.set(left - 1);
+            return Err(SendError {
+                reason: String::from("injected failure"),
+            });
+        }
+        self.send(msg);
+        Ok(())
+    }
 }
 
+pub mod notify;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,4 +90,15 @@ mod tests {
         // Check if messages were recorded
         assert_eq!(mock_messenger.sent_messages.borrow().len(), 2);
     }
+
+    #[test]
+    fn test_failure_injection() {
+        let mock = MockMessenger::new();
+        mock.fail_next(2);
+        assert!(mock.try_send("a").is_err());
+        assert!(mock.try_send("a").is_err());
+        assert_eq!(mock.try_send("a"), Ok(()));
+        assert_eq!(mock.attempts(), 3);
+        assert_eq!(*mock.sent_messages.borrow(), vec!["a"]);
+    }
 }
@@ -0,0 +1,270 @@
+// Notification Service: Summary (what) + Renderer (how) + Messenger (where)
+//
+// Ownership layout:
+// - Messengers are shared: the same Slack backend may serve several subscriptions,
+//   and a test wants to keep its own handle to inspect what was sent.
+//   => Rc<dyn Messenger> (shared ownership + dynamic dispatch).
+// - Each subscription owns its rule and renderer exclusively.
+//   => Box<dyn Fn>, Box<dyn Renderer> (single owner + dynamic dispatch).
+// - MockMessenger records messages through &self, thanks to RefCell/Cell.
+
+use std::rc::Rc;
+
+use topic05_traits_dyn_drop::render::{Message, PlainText, Renderer};
+use topic05_traits_dyn_drop::Summary;
+
+use crate::{Messenger, SendError};
+
+/// Which items a subscription wants
+pub enum Rule {
+    All,
+    /// Items carrying this tag (see Summary::tags)
+    Tag(String),
+    /// Items by this author (see Summary::summarize_author)
+    Author(String),
+    Custom(Box<dyn Fn(&Message) -> bool>),
+}
+
+impl Rule {
+    pub fn matches(&self, msg: &Message) -> bool {
+        match self {
+            Rule::All => true,
+            Rule::Tag(tag) => msg.tags.iter().any(|t| t == tag),
+            Rule::Author(author) => msg.author == *author,
+            Rule::Custom(f) => f(msg),
+        }
+    }
+}
+
+pub struct Subscription {
+    pub name: String,
+    messenger: Rc<dyn Messenger>,
+    rule: Rule,
+    renderer: Box<dyn Renderer>,
+}
+
+/// Outcome of delivering one item to one subscription
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delivery<'s> {
+    pub subscriber: &'s str,
+    pub attempts: usize,
+    /// The last error if every attempt failed
+    pub result: Result<(), SendError>,
+}
+
+impl<'s> Delivery<'s> {
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+pub struct NotificationService {
+    subscriptions: Vec<Subscription>,
+    max_attempts: usize,
+}
+
+impl Default for NotificationService {
+    fn default() -> NotificationService {
+        NotificationService::new()
+    }
+}
+
+impl NotificationService {
+    /// Up to 3 attempts per delivery
+    pub fn new() -> NotificationService {
+        NotificationService::with_max_attempts(3)
+    }
+
+    /// `max_attempts` includes the first try; 0 is treated as 1.
+    pub fn with_max_attempts(max_attempts: usize) -> NotificationService {
+        NotificationService {
+            subscriptions: Vec::new(),
+            max_attempts: max_attempts.max(1),
+        }
+    }
+
+    /// Subscribe with plain-text rendering ("Breaking news! ...")
+    pub fn subscribe(&mut self, name: &str, messenger: Rc<dyn Messenger>, rule: Rule) {
+        self.subscribe_with(name, messenger, rule, Box::new(PlainText));
+    }
+
+    pub fn subscribe_with(
+        &mut self,
+        name: &str,
+        messenger: Rc<dyn Messenger>,
+        rule: Rule,
+        renderer: Box<dyn Renderer>,
+    ) {
+        self.subscriptions.push(Subscription {
+            name: String::from(name),
+            messenger,
+            rule,
+            renderer,
+        });
+    }
+
+    pub fn subscriptions(&self) -> &[Subscription] {
+        &self.subscriptions
+    }
+
+    /// Deliver `item` to every matching subscription, in subscription order.
+    /// Works with both concrete types and `&dyn Summary`.
+    pub fn notify<S: Summary + ?Sized>(&self, item: &S) -> Vec<Delivery<'_>> {
+        let msg = Message::from_summary(item);
+        self.subscriptions
+            .iter()
+            .filter(|sub| sub.rule.matches(&msg))
+            .map(|sub| self.deliver(sub, &sub.renderer.render(&msg)))
+            .collect()
+    }
+
+    fn deliver<'s>(&self, sub: &'s Subscription, text: &str) -> Delivery<'s> {
+        let mut attempts = 0;
+        let result = loop {
+            attempts += 1;
+            match sub.messenger.try_send(text) {
+                Ok(()) => break Ok(()),
+                Err(e) if attempts >= self.max_attempts => break Err(e),
+                Err(_) => continue,
+            }
+        };
+        Delivery {
+            subscriber: &sub.name,
+            attempts,
+            result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockMessenger;
+    use topic05_traits_dyn_drop::render::{render_dynamic, Json};
+    use topic05_traits_dyn_drop::{notify_static, NewsArticle, Tweet, Wechat};
+
+    fn article() -> NewsArticle {
+        NewsArticle {
+            headline: String::from("Penguins win the Stanley Cup Championship!"),
+            location: String::from("Pittsburgh, PA, USA"),
+            author: String::from("Iceburgh"),
+            content: String::from("The Pittsburgh Penguins once again are the best hockey team in the NHL."),
+        }
+    }
+
+    fn tweet(reply: bool) -> Tweet {
+        Tweet {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course, as you probably already know, people"),
+            reply,
+            retweet: false,
+        }
+    }
+
+    fn wechat() -> Wechat {
+        Wechat {
+            official_account: String::from("RustLang"),
+            title: String::from("New release 1.80"),
+        }
+    }
+
+    #[test]
+    fn test_routing_by_rule() {
+        let news = Rc::new(MockMessenger::new());
+        let replies = Rc::new(MockMessenger::new());
+        let everything = Rc::new(MockMessenger::new());
+
+        let mut service = NotificationService::new();
+        service.subscribe("news", news.clone(), Rule::Tag(String::from("news")));
+        service.subscribe("replies", replies.clone(), Rule::Tag(String::from("reply")));
+        service.subscribe("all", everything.clone(), Rule::All);
+
+        let (article, reply, plain) = (article(), tweet(true), tweet(false));
+        let report = service.notify(&article);
+        let names: Vec<&str> = report.iter().map(|d| d.subscriber).collect();
+        assert_eq!(names, vec!["news", "all"]);
+
+        service.notify(&reply);
+        service.notify(&plain);
+
+        // The default renderer produces the same text as notify_static
+        assert_eq!(*news.sent_messages.borrow(), vec![notify_static(&article)]);
+        assert_eq!(*replies.sent_messages.borrow(), vec![notify_static(&reply)]);
+        assert_eq!(
+            *everything.sent_messages.borrow(),
+            vec![notify_static(&article), notify_static(&reply), notify_static(&plain)]
+        );
+    }
+
+    #[test]
+    fn test_author_and_custom_rules() {
+        let mock = Rc::new(MockMessenger::new());
+        let mut service = NotificationService::new();
+        service.subscribe("by-author", mock.clone(), Rule::Author(String::from("@horse_ebooks")));
+        service.subscribe(
+            "short",
+            mock.clone(),
+            Rule::Custom(Box::new(|msg| msg.summary.chars().count() < 70)),
+        );
+
+        assert_eq!(service.notify(&tweet(false)).len(), 2);
+        assert_eq!(service.notify(&article()).len(), 0);
+        assert_eq!(mock.sent_messages.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_renderer_per_subscription() {
+        let mock = Rc::new(MockMessenger::new());
+        let mut service = NotificationService::new();
+        service.subscribe_with("webhook", mock.clone(), Rule::All, Box::new(Json));
+
+        // dyn Summary works too
+        let item: Box<dyn Summary> = Box::new(wechat());
+        service.notify(&*item);
+        assert_eq!(*mock.sent_messages.borrow(), vec![render_dynamic(&Json, &*item)]);
+    }
+
+    #[test]
+    fn test_retries() {
+        let flaky = Rc::new(MockMessenger::new());
+        let down = Rc::new(MockMessenger::new());
+        let mut service = NotificationService::with_max_attempts(3);
+        service.subscribe("flaky", flaky.clone(), Rule::All);
+        service.subscribe("down", down.clone(), Rule::All);
+
+        flaky.fail_next(2);
+        down.fail_next(usize::MAX);
+        let report = service.notify(&article());
+
+        assert!(report[0].is_ok());
+        assert_eq!(report[0].attempts, 3);
+        assert_eq!(flaky.sent_messages.borrow().len(), 1);
+
+        // One failing subscription does not affect the others
+        assert!(!report[1].is_ok());
+        assert_eq!(report[1].attempts, 3);
+        assert_eq!(report[1].result.as_ref().unwrap_err().reason, "injected failure");
+        assert_eq!(down.attempts(), 3);
+        assert!(down.sent_messages.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_shared_messenger() {
+        let mock = Rc::new(MockMessenger::new());
+        let mut service = NotificationService::with_max_attempts(0);
+        service.subscribe("a", mock.clone(), Rule::All);
+        service.subscribe("b", mock.clone(), Rule::All);
+        // One for the test + one per subscription
+        assert_eq!(Rc::strong_count(&mock), 3);
+
+        mock.fail_next(1);
+        let report = service.notify(&article());
+        // max_attempts(0) is treated as 1: no retry
+        assert_eq!(report[0].attempts, 1);
+        assert!(!report[0].is_ok());
+        assert!(report[1].is_ok());
+
+        drop(service);
+        assert_eq!(Rc::strong_count(&mock), 1);
+    }
+}